@@ -0,0 +1,244 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::Duration,
+};
+use tauri::{AppHandle, Manager};
+
+use crate::{health::HealthMonitor, proxy, windows};
+
+// Servers send a heartbeat every 10s, so a quiet stream has stalled.
+const STALL_TIMEOUT: Duration = Duration::from_secs(30);
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+const LIST_TIMEOUT: Duration = Duration::from_secs(7);
+
+#[derive(serde::Deserialize)]
+struct PermissionRequest {
+    id: String,
+    #[serde(rename = "sessionID")]
+    session_id: String,
+}
+
+#[derive(serde::Deserialize)]
+struct GlobalEvent {
+    payload: Payload,
+}
+
+#[derive(serde::Deserialize)]
+struct Payload {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    properties: serde_json::Value,
+}
+
+#[derive(serde::Deserialize)]
+struct PermissionReplied {
+    #[serde(rename = "requestID")]
+    request_id: String,
+}
+
+/// Permission requests waiting for the user on each watched server, kept
+/// current from the servers' `/global/event` streams. The sessions they belong
+/// to are counted on the dock or launcher badge.
+#[derive(Default)]
+pub struct Approvals {
+    // Server URL -> request ID -> session ID. A server is followed while it
+    // has an entry.
+    pending: Mutex<HashMap<String, HashMap<String, String>>>,
+}
+
+impl Approvals {
+    fn sessions(&self) -> u32 {
+        self.pending
+            .lock()
+            .unwrap()
+            .values()
+            .flat_map(|v| v.values())
+            .collect::<HashSet<_>>()
+            .len() as u32
+    }
+
+    fn update(&self, url: &str, f: impl FnOnce(&mut HashMap<String, String>)) {
+        if let Some(pending) = self.pending.lock().unwrap().get_mut(url) {
+            f(pending);
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(Approvals::default());
+}
+
+/// Starts following every watched server that isn't followed yet. Runs after
+/// each health check round.
+pub fn sync(app: &AppHandle) {
+    let approvals = app.state::<Approvals>();
+    for (url, password) in app.state::<HealthMonitor>().servers() {
+        let mut pending = approvals.pending.lock().unwrap();
+        if pending.contains_key(&url) {
+            continue;
+        }
+        pending.insert(url.clone(), HashMap::new());
+        tokio::spawn(follow(app.clone(), url, password));
+    }
+
+    // Also restores the badge once a window is open again.
+    show(app);
+}
+
+fn show(app: &AppHandle) {
+    let count = app.state::<Approvals>().sessions();
+    if let Err(e) = windows::set_badge(app, count) {
+        tracing::debug!("{e}");
+    }
+}
+
+async fn follow(app: AppHandle, url: String, password: Option<String>) {
+    loop {
+        if let Err(e) = stream(&app, &url, password.as_deref()).await {
+            tracing::debug!(%url, "Event stream ended: {e}");
+        }
+
+        let watched = app
+            .state::<HealthMonitor>()
+            .servers()
+            .iter()
+            .any(|(v, _)| *v == url);
+        {
+            let approvals = app.state::<Approvals>();
+            let mut pending = approvals.pending.lock().unwrap();
+            if watched {
+                pending.insert(url.clone(), HashMap::new());
+            } else {
+                pending.remove(&url);
+            }
+        }
+        show(&app);
+
+        if !watched {
+            return;
+        }
+        tokio::time::sleep(RETRY_DELAY).await;
+    }
+}
+
+async fn stream(app: &AppHandle, url: &str, password: Option<&str>) -> Result<(), String> {
+    let base = reqwest::Url::parse(url).map_err(|e| format!("Invalid server URL: {}", e))?;
+    let client = proxy::client_builder(&base)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let get = |path: &str| {
+        let mut req = client.get(
+            base.join(path)
+                .map_err(|e| format!("Invalid server URL: {}", e))?,
+        );
+        if let Some(password) = password {
+            req = req.basic_auth("opencode", Some(password));
+        }
+        Ok::<_, String>(req)
+    };
+
+    let mut res = get("/global/event")?
+        .send()
+        .await
+        .map_err(|e| format!("Event request failed: {}", e))?;
+    if !res.status().is_success() {
+        return Err(format!("Event stream returned {}", res.status()));
+    }
+
+    // Requests asked before we subscribed. Listed after subscribing so none
+    // fall in between.
+    let listed = get("/permission")?
+        .timeout(LIST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Permission request failed: {}", e))?
+        .text()
+        .await
+        .unwrap_or_default();
+    let listed = serde_json::from_str::<Vec<PermissionRequest>>(&listed).unwrap_or_default();
+    let approvals = app.state::<Approvals>();
+    approvals.update(url, |pending| {
+        pending.extend(listed.into_iter().map(|v| (v.id, v.session_id)))
+    });
+    show(app);
+
+    let mut buf = Vec::new();
+    loop {
+        let Some(chunk) = tokio::time::timeout(STALL_TIMEOUT, res.chunk())
+            .await
+            .map_err(|_| "Event stream stalled".to_string())?
+            .map_err(|e| format!("Event stream failed: {}", e))?
+        else {
+            return Ok(());
+        };
+        buf.extend_from_slice(&chunk);
+
+        let mut changed = false;
+        while let Some(end) = buf.iter().position(|b| *b == b'\n') {
+            let line = buf.drain(..=end).collect::<Vec<_>>();
+            let Some(data) = std::str::from_utf8(&line)
+                .ok()
+                .and_then(|v| v.trim_end().strip_prefix("data:"))
+            else {
+                continue;
+            };
+            approvals.update(url, |pending| changed |= apply(pending, data.trim()));
+        }
+        if changed {
+            show(app);
+        }
+    }
+}
+
+/// Applies one event to a server's pending requests. Returns whether it
+/// changed them.
+fn apply(pending: &mut HashMap<String, String>, data: &str) -> bool {
+    let Ok(event) = serde_json::from_str::<GlobalEvent>(data) else {
+        return false;
+    };
+
+    match event.payload.kind.as_str() {
+        "permission.asked" => serde_json::from_value::<PermissionRequest>(event.payload.properties)
+            .is_ok_and(|v| pending.insert(v.id, v.session_id).is_none()),
+        "permission.replied" => {
+            serde_json::from_value::<PermissionReplied>(event.payload.properties)
+                .is_ok_and(|v| pending.remove(&v.request_id).is_some())
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asked(id: &str, session: &str) -> String {
+        format!(
+            r#"{{"directory":"/p","payload":{{"type":"permission.asked","properties":{{"id":"{id}","sessionID":"{session}","permission":"bash","patterns":[],"metadata":{{}},"always":[]}}}}}}"#
+        )
+    }
+
+    #[test]
+    fn tracks_asked_and_replied_requests() {
+        let mut pending = HashMap::new();
+
+        assert!(apply(&mut pending, &asked("per_1", "ses_1")));
+        assert!(apply(&mut pending, &asked("per_2", "ses_1")));
+        assert!(!apply(
+            &mut pending,
+            r#"{"payload":{"type":"server.heartbeat","properties":{}}}"#
+        ));
+        assert_eq!(pending.len(), 2);
+
+        assert!(apply(
+            &mut pending,
+            r#"{"directory":"/p","payload":{"type":"permission.replied","properties":{"sessionID":"ses_1","requestID":"per_1","reply":"once"}}}"#
+        ));
+        assert_eq!(
+            pending.into_iter().collect::<Vec<_>>(),
+            [("per_2".to_string(), "ses_1".to_string())]
+        );
+    }
+}
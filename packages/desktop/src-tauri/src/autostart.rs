@@ -0,0 +1,293 @@
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::constants::{BACKGROUND_MODE_KEY, SETTINGS_STORE};
+
+/// Passed by the login item so the app can tell it was started at login.
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// Whether closing the last window should keep the app and its local server
+/// running. macOS only: the Dock icon brings a window back, while elsewhere
+/// there'd be no tray icon to reach the hidden app.
+pub fn background_mode(app: &AppHandle) -> bool {
+    cfg!(target_os = "macos")
+        && app
+            .store(SETTINGS_STORE)
+            .ok()
+            .and_then(|store| store.get(BACKGROUND_MODE_KEY))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+}
+
+/// Launched at login with background mode on: start the server but no window.
+pub fn start_hidden(app: &AppHandle) -> bool {
+    std::env::args().any(|v| v == AUTOSTART_ARG) && background_mode(app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_background_mode(app: AppHandle) -> bool {
+    background_mode(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_background_mode(app: AppHandle, enabled: bool) -> Result<(), String> {
+    write_background_mode(&app, enabled)
+}
+
+pub fn write_background_mode(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    if enabled && !cfg!(target_os = "macos") {
+        return Err("Background mode is only available on macOS".to_string());
+    }
+
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    store.set(BACKGROUND_MODE_KEY, serde_json::Value::Bool(enabled));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_autostart(app: AppHandle) -> bool {
+    is_enabled(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_autostart(app: AppHandle, enabled: bool) -> Result<(), String> {
+    if enabled {
+        enable(&app, &executable()?)?;
+    } else {
+        disable(&app)?;
+    }
+
+    tracing::info!(enabled, "Launch at login changed");
+
+    Ok(())
+}
+
+pub fn executable() -> Result<String, String> {
+    // AppImages run from a temporary mount, so point at the image itself.
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(appimage.to_string_lossy().to_string());
+    }
+
+    std::env::current_exe()
+        .map(|v| v.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to resolve executable: {}", e))
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn launch_agent(label: &str, exe: &str) -> String {
+    let escape = |v: &str| {
+        v.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>{}</string>
+  <key>ProgramArguments</key>
+  <array>
+    <string>{}</string>
+    <string>{AUTOSTART_ARG}</string>
+  </array>
+  <key>RunAtLoad</key>
+  <true/>
+</dict>
+</plist>
+"#,
+        escape(label),
+        escape(exe)
+    )
+}
+
+/// Quotes a path for a desktop entry `Exec` key. The Desktop Entry spec
+/// escapes twice: ", `, $ and \ are backslash-escaped inside the quotes, and
+/// then every backslash is escaped again because `Exec` is a string value.
+/// A literal % is written %% so it isn't read as a field code.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn quote_exec(exe: &str) -> String {
+    let quoted = exe
+        .chars()
+        .flat_map(|c| match c {
+            '"' | '`' | '$' | '\\' => vec!['\\', c],
+            '%' => vec!['%', '%'],
+            c => vec![c],
+        })
+        .collect::<String>();
+
+    format!("\"{}\"", quoted.replace('\\', "\\\\"))
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn desktop_entry(name: &str, exe: &str) -> String {
+    format!(
+        "[Desktop Entry]\nType=Application\nName={name}\nExec={} {AUTOSTART_ARG}\nX-GNOME-Autostart-enabled=true\n",
+        quote_exec(exe)
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn entry_path(app: &AppHandle) -> Option<PathBuf> {
+    let label = &app.config().identifier;
+    Some(
+        dirs::home_dir()?
+            .join("Library/LaunchAgents")
+            .join(format!("{label}.plist")),
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn entry_path(app: &AppHandle) -> Option<PathBuf> {
+    let label = &app.config().identifier;
+    Some(
+        dirs::config_dir()?
+            .join("autostart")
+            .join(format!("{label}.desktop")),
+    )
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn is_enabled(app: &AppHandle) -> bool {
+    entry_path(app).is_some_and(|v| v.exists())
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn enable(app: &AppHandle, exe: &str) -> Result<(), String> {
+    let path = entry_path(app).ok_or_else(|| "Could not resolve autostart dir".to_string())?;
+
+    #[cfg(target_os = "macos")]
+    let contents = launch_agent(&app.config().identifier, exe);
+    #[cfg(target_os = "linux")]
+    let contents = desktop_entry(
+        app.config().product_name.as_deref().unwrap_or("OpenCode"),
+        exe,
+    );
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create autostart dir: {}", e))?;
+    }
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write autostart entry: {}", e))
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn disable(app: &AppHandle) -> Result<(), String> {
+    let Some(path) = entry_path(app) else {
+        return Ok(());
+    };
+
+    match std::fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(format!("Failed to remove autostart entry: {}", e))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(windows)]
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+#[cfg(windows)]
+fn value_name(app: &AppHandle) -> String {
+    app.config()
+        .product_name
+        .clone()
+        .unwrap_or_else(|| "OpenCode".to_string())
+}
+
+#[cfg(windows)]
+fn reg(args: &[&str]) -> Result<(), String> {
+    let output = std::process::Command::new("reg")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn is_enabled(app: &AppHandle) -> bool {
+    reg(&["query", RUN_KEY, "/v", &value_name(app)]).is_ok()
+}
+
+#[cfg(windows)]
+fn enable(app: &AppHandle, exe: &str) -> Result<(), String> {
+    let command = format!("\"{exe}\" {AUTOSTART_ARG}");
+    reg(&[
+        "add",
+        RUN_KEY,
+        "/v",
+        &value_name(app),
+        "/t",
+        "REG_SZ",
+        "/d",
+        &command,
+        "/f",
+    ])
+}
+
+#[cfg(windows)]
+fn disable(app: &AppHandle) -> Result<(), String> {
+    if !is_enabled(app) {
+        return Ok(());
+    }
+
+    reg(&["delete", RUN_KEY, "/v", &value_name(app), "/f"])
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn is_enabled(_app: &AppHandle) -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn enable(_app: &AppHandle, _exe: &str) -> Result<(), String> {
+    Err("Launch at login is not supported on this platform".to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn disable(_app: &AppHandle) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_agent_escapes_paths() {
+        let plist = launch_agent("ai.opencode.desktop", "/Apps/R&D/OpenCode");
+        assert!(plist.contains("<string>/Apps/R&amp;D/OpenCode</string>"));
+        assert!(plist.contains("<string>--autostart</string>"));
+    }
+
+    #[test]
+    fn desktop_entry_quotes_exec() {
+        let entry = desktop_entry("OpenCode", "/opt/my $apps/opencode");
+        assert!(entry.contains("Exec=\"/opt/my \\\\$apps/opencode\" --autostart\n"));
+
+        assert_eq!(quote_exec("/opt/a\\b"), "\"/opt/a\\\\\\\\b\"");
+        assert_eq!(quote_exec("/opt/\"x\""), "\"/opt/\\\\\"x\\\\\"\"");
+        assert_eq!(quote_exec("/opt/100%/opencode"), "\"/opt/100%%/opencode\"");
+    }
+}
@@ -0,0 +1,153 @@
+use std::{path::PathBuf, sync::RwLock};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::constants::{EXTRA_CA_CERTS_KEY, SETTINGS_STORE};
+
+const BUNDLE_FILE: &str = "extra-ca-certs.pem";
+const PEM_HEADER: &str = "-----BEGIN CERTIFICATE-----";
+
+struct Loaded {
+    bundle: PathBuf,
+    certs: Vec<reqwest::Certificate>,
+}
+
+static LOADED: RwLock<Option<Loaded>> = RwLock::new(None);
+
+/// Trusts the configured CAs in addition to the built-in roots.
+pub fn add_roots(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    match LOADED.read().unwrap().as_ref() {
+        Some(loaded) => loaded
+            .certs
+            .iter()
+            .cloned()
+            .fold(builder, |builder, cert| builder.add_root_certificate(cert)),
+        None => builder,
+    }
+}
+
+/// Every configured CA in one PEM file, for `NODE_EXTRA_CA_CERTS`.
+pub fn bundle() -> Option<PathBuf> {
+    LOADED.read().unwrap().as_ref().map(|v| v.bundle.clone())
+}
+
+fn read_pem(path: &str) -> Result<String, String> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if !contents.contains(PEM_HEADER) {
+        return Err(format!("{} is not a PEM certificate file", path));
+    }
+    Ok(contents)
+}
+
+fn concat(files: &[String]) -> String {
+    files
+        .iter()
+        .map(|v| v.trim())
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+// The Bun-built sidecar reads a single NODE_EXTRA_CA_CERTS file, so one the
+// user already set goes into the bundle rather than being replaced by it.
+fn apply(app: &AppHandle, paths: &[String]) -> Result<(), String> {
+    if paths.is_empty() {
+        *LOADED.write().unwrap() = None;
+        return Ok(());
+    }
+
+    let mut files = paths
+        .iter()
+        .map(|v| read_pem(v))
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Ok(inherited) = std::env::var("NODE_EXTRA_CA_CERTS")
+        && let Ok(contents) = read_pem(&inherited)
+    {
+        files.push(contents);
+    }
+
+    let contents = concat(&files);
+    let certs = reqwest::Certificate::from_pem_bundle(contents.as_bytes())
+        .map_err(|e| format!("Failed to parse certificates: {}", e))?;
+
+    let dir = app
+        .path()
+        .app_local_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let bundle = dir.join(BUNDLE_FILE);
+    std::fs::write(&bundle, contents)
+        .map_err(|e| format!("Failed to write {}: {}", bundle.display(), e))?;
+
+    tracing::info!(count = certs.len(), "Loaded extra CA certificates");
+    *LOADED.write().unwrap() = Some(Loaded { bundle, certs });
+    Ok(())
+}
+
+/// Loads the configured CAs before any server is spawned or request is made.
+pub fn init(app: &AppHandle) {
+    if let Err(e) = apply(app, &configured(app)) {
+        tracing::warn!("Ignoring extra CA certificates: {e}");
+    }
+}
+
+/// The PEM files the user asked to trust.
+pub fn configured(app: &AppHandle) -> Vec<String> {
+    app.store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(EXTRA_CA_CERTS_KEY))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_extra_ca_certs(app: AppHandle) -> Vec<String> {
+    configured(&app)
+}
+
+/// Sets the PEM files to trust on top of the system roots. Applies to the
+/// app's own requests immediately and to servers spawned from now on.
+#[tauri::command]
+#[specta::specta]
+pub fn set_extra_ca_certs(app: AppHandle, paths: Vec<String>) -> Result<(), String> {
+    let paths = paths
+        .into_iter()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>();
+    apply(&app, &paths)?;
+
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    let value = serde_json::to_value(&paths)
+        .map_err(|e| format!("Failed to serialize certificate paths: {}", e))?;
+    store.set(EXTRA_CA_CERTS_KEY, value);
+
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concatenates_pem_files_on_separate_lines() {
+        let files = [
+            format!("{PEM_HEADER}\nA\n-----END CERTIFICATE-----"),
+            format!("\n{PEM_HEADER}\nB\n-----END CERTIFICATE-----\n\n"),
+        ];
+        assert_eq!(
+            concat(&files),
+            format!(
+                "{PEM_HEADER}\nA\n-----END CERTIFICATE-----\n{PEM_HEADER}\nB\n-----END CERTIFICATE-----\n"
+            )
+        );
+    }
+}
@@ -7,7 +7,7 @@ use process_wrap::tokio::{CommandWrapper, JobObject, KillOnDrop};
 #[cfg(unix)]
 use std::os::unix::process::ExitStatusExt;
 use std::sync::Arc;
-use std::{process::Stdio, time::Duration};
+use std::{collections::VecDeque, process::Stdio, time::Duration};
 use tauri::{AppHandle, Manager, path::BaseDirectory};
 use tauri_specta::Event;
 use tokio::{
@@ -21,7 +21,9 @@ use tracing::Instrument;
 #[cfg(windows)]
 use windows::Win32::System::Threading::{CREATE_NO_WINDOW, CREATE_SUSPENDED};
 
+use crate::env_profiles;
 use crate::server::get_wsl_config;
+use crate::server_logs::{LogStream, ServerLogs};
 
 #[cfg(windows)]
 #[derive(Clone, Copy, Debug)]
@@ -39,6 +41,7 @@ impl CommandWrapper for WinCreationFlags {
 
 const CLI_INSTALL_DIR: &str = ".opencode/bin";
 const CLI_BINARY_NAME: &str = "opencode";
+const STDERR_TAIL_LINES: usize = 50;
 
 #[derive(serde::Deserialize, Debug)]
 pub struct ServerConfig {
@@ -65,6 +68,13 @@ pub struct TerminatedPayload {
     pub signal: Option<i32>,
 }
 
+/// How a `serve` process ended, with the tail of its stderr for crash reports.
+#[derive(Clone, Debug)]
+pub struct ServeExit {
+    pub payload: TerminatedPayload,
+    pub stderr: Vec<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct CommandChild {
     kill: mpsc::Sender<()>,
@@ -79,7 +89,7 @@ impl CommandChild {
 }
 
 pub async fn get_config(app: &AppHandle) -> Option<Config> {
-    let (events, _) = spawn_command(app, "debug config", &[]).ok()?;
+    let (events, _) = spawn_command(app, "debug config", &[], None).ok()?;
 
     events
         .fold(String::new(), async |mut config_str, event| {
@@ -221,7 +231,7 @@ fn is_wsl_enabled(_app: &tauri::AppHandle) -> bool {
     get_wsl_config(_app.clone()).is_ok_and(|v| v.enabled)
 }
 
-fn shell_escape(input: &str) -> String {
+pub fn shell_escape(input: &str) -> String {
     if input.is_empty() {
         return "''".to_string();
     }
@@ -236,6 +246,7 @@ pub fn spawn_command(
     app: &tauri::AppHandle,
     args: &str,
     extra_env: &[(&str, String)],
+    current_dir: Option<&std::path::Path>,
 ) -> Result<(impl Stream<Item = CommandEvent> + 'static, CommandChild), std::io::Error> {
     let state_dir = app
         .path()
@@ -257,6 +268,23 @@ pub fn spawn_command(
             state_dir.to_string_lossy().to_string(),
         ),
     ];
+    // Without a proxy the inherited variables are removed too, so turning the
+    // proxy off in settings also applies to a proxy set in the environment.
+    let mut unset_env = Vec::new();
+    match crate::proxy::server_proxy() {
+        Some(proxy) => {
+            envs.extend(crate::proxy::ENV_KEYS.map(|key| (key.to_string(), proxy.clone())))
+        }
+        None => unset_env.extend(crate::proxy::ENV_KEYS),
+    }
+    let no_proxy = crate::proxy::no_proxy();
+    envs.extend(["NO_PROXY", "no_proxy"].map(|key| (key.to_string(), no_proxy.clone())));
+    if let Some(bundle) = crate::certs::bundle() {
+        envs.push((
+            "NODE_EXTRA_CA_CERTS".to_string(),
+            bundle.to_string_lossy().to_string(),
+        ));
+    }
     envs.extend(
         extra_env
             .iter()
@@ -278,6 +306,24 @@ pub fn spawn_command(
                 "fi".to_string(),
             ];
 
+            // The CA bundle is written on the Windows side; the CLI inside WSL
+            // needs its /mnt path.
+            envs.retain_mut(|(key, value)| {
+                if key != "NODE_EXTRA_CA_CERTS" {
+                    return true;
+                }
+                match crate::convert_wsl_path(value, crate::WslPathMode::Linux) {
+                    Ok(path) => {
+                        *value = path;
+                        true
+                    }
+                    Err(e) => {
+                        tracing::warn!("Not passing extra CA certificates to WSL: {e}");
+                        false
+                    }
+                }
+            });
+
             let mut env_prefix = vec![
                 "OPENCODE_EXPERIMENTAL_ICON_DISCOVERY=true".to_string(),
                 "OPENCODE_EXPERIMENTAL_FILEWATCHER=true".to_string(),
@@ -293,6 +339,9 @@ pub fn spawn_command(
                     .map(|(key, value)| format!("{}={}", key, shell_escape(value))),
             );
 
+            if !unset_env.is_empty() {
+                script.push(format!("unset {}", unset_env.join(" ")));
+            }
             script.push(format!("{} exec \"$BIN\" {}", env_prefix.join(" "), args));
 
             let mut cmd = Command::new("wsl");
@@ -303,6 +352,9 @@ pub fn spawn_command(
             let mut cmd = Command::new(sidecar);
             cmd.args(args.split_whitespace());
 
+            for key in unset_env {
+                cmd.env_remove(key);
+            }
             for (key, value) in envs {
                 cmd.env(key, value);
             }
@@ -322,6 +374,9 @@ pub fn spawn_command(
         let mut cmd = Command::new(shell);
         cmd.args(["-l", "-c", &line]);
 
+        for key in unset_env {
+            cmd.env_remove(key);
+        }
         for (key, value) in envs {
             cmd.env(key, value);
         }
@@ -329,6 +384,9 @@ pub fn spawn_command(
         cmd
     };
 
+    if let Some(dir) = current_dir {
+        cmd.current_dir(dir);
+    }
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
     cmd.stdin(Stdio::null());
@@ -422,33 +480,57 @@ pub fn serve(
     hostname: &str,
     port: u32,
     password: &str,
-) -> (CommandChild, oneshot::Receiver<TerminatedPayload>) {
-    let (exit_tx, exit_rx) = oneshot::channel::<TerminatedPayload>();
-
-    tracing::info!(port, "Spawning sidecar");
-
-    let envs = [
+    directory: Option<&str>,
+) -> Result<(CommandChild, oneshot::Receiver<ServeExit>), String> {
+    let (exit_tx, exit_rx) = oneshot::channel::<ServeExit>();
+
+    tracing::info!(port, ?directory, "Spawning sidecar");
+
+    let profile = directory
+        .map(|v| env_profiles::load(app, v))
+        .unwrap_or_default();
+    let mut envs = profile
+        .iter()
+        .map(|v| (v.key.as_str(), v.value.clone()))
+        .collect::<Vec<_>>();
+    envs.extend([
         ("OPENCODE_SERVER_USERNAME", "opencode".to_string()),
         ("OPENCODE_SERVER_PASSWORD", password.to_string()),
-    ];
+    ]);
 
     let (events, child) = spawn_command(
         app,
         format!("--print-logs --log-level WARN serve --hostname {hostname} --port {port}").as_str(),
         &envs,
+        directory.map(std::path::Path::new),
     )
-    .expect("Failed to spawn opencode");
+    .map_err(|e| format!("Failed to spawn opencode: {}", e))?;
 
     let mut exit_tx = Some(exit_tx);
+    let mut stderr = VecDeque::with_capacity(STDERR_TAIL_LINES);
+    let app = app.clone();
+    let directory = directory.map(str::to_string);
+    let capture = move |stream, line: &str| {
+        if let Some(logs) = app.try_state::<ServerLogs>() {
+            logs.push(stream, directory.as_deref(), line);
+        }
+    };
     tokio::spawn(
         events
             .for_each(move |event| {
                 match event {
                     CommandEvent::Stdout(line) => {
                         tracing::info!("{line}");
+                        capture(LogStream::Stdout, &line);
                     }
                     CommandEvent::Stderr(line) => {
                         tracing::info!("{line}");
+                        capture(LogStream::Stderr, &line);
+
+                        if stderr.len() == STDERR_TAIL_LINES {
+                            stderr.pop_front();
+                        }
+                        stderr.push_back(line);
                     }
                     CommandEvent::Error(err) => {
                         tracing::error!("{err}");
@@ -461,7 +543,10 @@ pub fn serve(
                         );
 
                         if let Some(tx) = exit_tx.take() {
-                            let _ = tx.send(payload);
+                            let _ = tx.send(ServeExit {
+                                payload,
+                                stderr: stderr.drain(..).collect(),
+                            });
                         }
                     }
                 }
@@ -471,7 +556,7 @@ pub fn serve(
             .instrument(tracing::info_span!("sidecar")),
     );
 
-    (child, exit_rx)
+    Ok((child, exit_rx))
 }
 
 pub mod sqlite_migration {
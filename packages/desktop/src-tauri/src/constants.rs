@@ -3,6 +3,21 @@ use tauri_plugin_window_state::StateFlags;
 pub const SETTINGS_STORE: &str = "opencode.settings.dat";
 pub const DEFAULT_SERVER_URL_KEY: &str = "defaultServerUrl";
 pub const WSL_ENABLED_KEY: &str = "wslEnabled";
+pub const SCHEMA_VERSION_KEY: &str = "schemaVersion";
+pub const PRIVACY_MODE_KEY: &str = "privacyMode";
+pub const DEEP_LINK_POLICY_KEY: &str = "deepLinkPolicy";
+pub const LOCAL_SERVER_KEY: &str = "localServer";
+pub const BACKGROUND_MODE_KEY: &str = "backgroundMode";
+pub const PREVENT_SLEEP_KEY: &str = "preventSleep";
+pub const RECENT_PROJECTS_KEY: &str = "recentProjects";
+pub const TRUSTED_WORKSPACES_KEY: &str = "trustedWorkspaces";
+pub const ENV_PROFILES_KEY: &str = "envProfiles";
+pub const PROXY_CONFIG_KEY: &str = "proxy";
+pub const EXTRA_CA_CERTS_KEY: &str = "extraCaCerts";
+pub const UPDATE_CHANNEL_KEY: &str = "updateChannel";
+pub const UPDATE_DEFERRAL_KEY: &str = "updateDeferral";
+/// Where "Send Report" posts crash reports; without it reports stay local.
+pub const CRASH_REPORT_URL: Option<&str> = option_env!("OPENCODE_CRASH_REPORT_URL");
 pub const UPDATER_ENABLED: bool = option_env!("TAURI_SIGNING_PRIVATE_KEY").is_some();
 
 pub fn window_state_flags() -> StateFlags {
@@ -0,0 +1,205 @@
+use std::{
+    backtrace::Backtrace,
+    path::{Path, PathBuf},
+};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogResult};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::{constants::CRASH_REPORT_URL, logging, privacy, proxy};
+
+const CRASH_DIR: &str = "crashes";
+// Written by the panic hook, renamed to `.txt` once the user has seen it.
+const PENDING_EXT: &str = "pending";
+const REPORT_EXT: &str = "txt";
+const BREADCRUMB_LINES: usize = 200;
+const MAX_REPORTS: usize = 20;
+
+fn payload_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
+
+fn report(message: &str, location: &str, backtrace: &str, breadcrumbs: &str) -> String {
+    format!(
+        "opencode desktop {version} ({os} {arch})\n\
+         time: {time}\n\
+         thread: {thread}\n\
+         panic: {message}\n\
+         at: {location}\n\
+         \n\
+         backtrace:\n{backtrace}\n\
+         \n\
+         recent log:\n{breadcrumbs}\n",
+        version = env!("CARGO_PKG_VERSION"),
+        os = std::env::consts::OS,
+        arch = std::env::consts::ARCH,
+        time = chrono::Utc::now().to_rfc3339(),
+        thread = std::thread::current().name().unwrap_or("<unnamed>"),
+    )
+}
+
+fn breadcrumbs() -> String {
+    let log = logging::tail();
+    let lines = log.lines().collect::<Vec<_>>();
+    lines[lines.len().saturating_sub(BREADCRUMB_LINES)..].join("\n")
+}
+
+/// Writes a report to `<log dir>/crashes` for every panic, then runs the
+/// default hook. Native crashes (segfaults, aborts) are not captured.
+pub fn install(log_dir: &Path) {
+    let dir = log_dir.join(CRASH_DIR);
+    let default_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let location = info
+            .location()
+            .map(|v| format!("{}:{}", v.file(), v.line()))
+            .unwrap_or_default();
+        let contents = privacy::scrub(&report(
+            payload_message(info.payload()),
+            &location,
+            &Backtrace::force_capture().to_string(),
+            &breadcrumbs(),
+        ));
+
+        let path = dir.join(format!(
+            "crash-{}.{PENDING_EXT}",
+            chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")
+        ));
+        if std::fs::create_dir_all(&dir)
+            .and_then(|_| std::fs::write(&path, contents))
+            .is_ok()
+        {
+            tracing::error!(path = %path.display(), "Crash report written");
+        }
+
+        default_hook(info);
+    }));
+}
+
+fn reports(dir: &Path, ext: &str) -> Vec<PathBuf> {
+    let mut paths = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|v| v.path())
+                .filter(|v| v.extension().is_some_and(|v| v == ext))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // Timestamped names sort chronologically.
+    paths.sort();
+    paths
+}
+
+async fn upload(url: &str, contents: String) -> Result<(), String> {
+    let url = reqwest::Url::parse(url).map_err(|e| format!("Invalid report URL: {}", e))?;
+    let client = proxy::client_builder(&url)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+    client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(contents)
+        .send()
+        .await
+        .and_then(|v| v.error_for_status())
+        .map_err(|e| format!("Failed to upload crash report: {}", e))?;
+
+    Ok(())
+}
+
+/// Asks about reports left by the previous run. Nothing leaves the machine
+/// unless the user chooses to send it.
+pub fn init(app: &AppHandle) {
+    let Ok(dir) = app.path().app_log_dir().map(|v| v.join(CRASH_DIR)) else {
+        return;
+    };
+    let pending = reports(&dir, PENDING_EXT);
+    let Some(latest) = pending.last().cloned() else {
+        return;
+    };
+
+    // Only ever ask once per report.
+    for path in &pending {
+        let _ = std::fs::rename(path, path.with_extension(REPORT_EXT));
+    }
+    let latest = latest.with_extension(REPORT_EXT);
+
+    let reviewed = reports(&dir, REPORT_EXT);
+    for path in &reviewed[..reviewed.len().saturating_sub(MAX_REPORTS)] {
+        let _ = std::fs::remove_file(path);
+    }
+
+    let app = app.clone();
+    tokio::spawn(async move {
+        const SEND: &str = "Send Report";
+        const SHOW: &str = "Show Report";
+
+        let action = if CRASH_REPORT_URL.is_some() {
+            SEND
+        } else {
+            SHOW
+        };
+        let dialog_app = app.clone();
+        let res = tokio::task::spawn_blocking(move || {
+            dialog_app
+                .dialog()
+                .message(format!(
+                    "OpenCode quit unexpectedly last time.\n\n{}",
+                    if CRASH_REPORT_URL.is_some() {
+                        "Would you like to send the crash report to help fix the problem? It contains the error, a backtrace and recent log lines, but no project content."
+                    } else {
+                        "A crash report with the error, a backtrace and recent log lines was saved. You can attach it when reporting the problem."
+                    }
+                ))
+                .title("Crash Report")
+                .buttons(MessageDialogButtons::OkCancelCustom(
+                    action.to_string(),
+                    "Dismiss".to_string(),
+                ))
+                .blocking_show_with_result()
+        })
+        .await;
+
+        if !matches!(res, Ok(MessageDialogResult::Custom(name)) if name == action) {
+            return;
+        }
+
+        let result = match CRASH_REPORT_URL {
+            Some(url) => match std::fs::read_to_string(&latest) {
+                Ok(contents) => upload(url, contents).await,
+                Err(e) => Err(format!("Failed to read crash report: {}", e)),
+            },
+            None => app
+                .opener()
+                .reveal_item_in_dir(&latest)
+                .map_err(|e| format!("Failed to show crash report: {}", e)),
+        };
+        if let Err(e) = result {
+            tracing::warn!("{e}");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_str_and_string_payloads() {
+        let payload: Box<dyn std::any::Any + Send> = Box::new("boom");
+        assert_eq!(payload_message(payload.as_ref()), "boom");
+
+        let payload: Box<dyn std::any::Any + Send> = Box::new(String::from("bang"));
+        assert_eq!(payload_message(payload.as_ref()), "bang");
+
+        let payload: Box<dyn std::any::Any + Send> = Box::new(42);
+        assert_eq!(payload_message(payload.as_ref()), "Box<dyn Any>");
+    }
+}
@@ -0,0 +1,1211 @@
+use reqwest::Url;
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_specta::Event;
+
+const SCHEME: &str = "opencode";
+const WEB_HOSTS: [&str; 2] = ["opencode.ai", "www.opencode.ai"];
+const MAX_PROMPT_LEN: usize = 16 * 1024;
+const AUTH_STATE_TTL: Duration = Duration::from_secs(10 * 60);
+const SHARE_LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeepLinkAction {
+    OpenProject {
+        directory: String,
+        session: Option<String>,
+    },
+    OpenShare {
+        id: String,
+        url: String,
+    },
+    NewSession {
+        directory: String,
+        prompt: Option<String>,
+        model: Option<String>,
+        agent: Option<String>,
+    },
+    OpenFile {
+        directory: String,
+        path: String,
+        line: Option<u32>,
+        column: Option<u32>,
+        session: Option<String>,
+    },
+    AuthCallback {
+        provider: String,
+        code: String,
+        state: String,
+    },
+    RunCommand {
+        command: String,
+        args: Option<String>,
+        directory: String,
+    },
+}
+
+impl DeepLinkAction {
+    pub fn from_url(url: &Url) -> Option<Self> {
+        match url.scheme() {
+            SCHEME => Self::from_scheme_url(url),
+            "https" => Self::from_web_url(url),
+            _ => None,
+        }
+    }
+
+    // opencode://<action>?<params>
+    fn from_scheme_url(url: &Url) -> Option<Self> {
+        match url.host_str()? {
+            "open-project" => Some(Self::OpenProject {
+                directory: query(url, "directory")?,
+                session: query(url, "session"),
+            }),
+            "new-session" => Some(Self::NewSession {
+                directory: query(url, "directory")?,
+                prompt: query(url, "prompt"),
+                model: query(url, "model"),
+                agent: query(url, "agent"),
+            })
+            .filter(Self::is_valid),
+            "open-file" => {
+                let directory = query(url, "directory")?;
+                Some(Self::OpenFile {
+                    path: resolve_in(&directory, &query(url, "path")?)?,
+                    directory,
+                    line: query(url, "line").and_then(|v| v.parse().ok()),
+                    column: query(url, "column").and_then(|v| v.parse().ok()),
+                    session: query(url, "session"),
+                })
+            }
+            "run" => Some(Self::RunCommand {
+                command: query(url, "command")?,
+                args: query(url, "args"),
+                directory: query(url, "directory")?,
+            })
+            .filter(Self::is_valid),
+            "auth" if url.path() == "/callback" => Some(Self::AuthCallback {
+                provider: query(url, "provider")?,
+                code: query(url, "code")?,
+                state: query(url, "state")?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The canonical URL for this action, with every parameter percent-encoded.
+    /// Auth callbacks are one-shot and can't be shared.
+    pub fn to_url(&self) -> Option<Url> {
+        let (action, params): (&str, Vec<(&str, Option<String>)>) = match self {
+            Self::OpenProject { directory, session } => (
+                "open-project",
+                vec![
+                    ("directory", Some(directory.clone())),
+                    ("session", session.clone()),
+                ],
+            ),
+            Self::NewSession {
+                directory,
+                prompt,
+                model,
+                agent,
+            } => (
+                "new-session",
+                vec![
+                    ("directory", Some(directory.clone())),
+                    ("prompt", prompt.clone()),
+                    ("model", model.clone()),
+                    ("agent", agent.clone()),
+                ],
+            ),
+            Self::OpenFile {
+                directory,
+                path,
+                line,
+                column,
+                session,
+            } => (
+                "open-file",
+                vec![
+                    ("directory", Some(directory.clone())),
+                    ("path", Some(path.clone())),
+                    ("line", line.map(|v| v.to_string())),
+                    ("column", column.map(|v| v.to_string())),
+                    ("session", session.clone()),
+                ],
+            ),
+            Self::RunCommand {
+                command,
+                args,
+                directory,
+            } => (
+                "run",
+                vec![
+                    ("command", Some(command.clone())),
+                    ("args", args.clone()),
+                    ("directory", Some(directory.clone())),
+                ],
+            ),
+            Self::OpenShare { url, .. } => return Url::parse(url).ok(),
+            Self::AuthCallback { .. } => return None,
+        };
+
+        let mut url = Url::parse(&format!("{SCHEME}://{action}")).ok()?;
+        url.query_pairs_mut().extend_pairs(
+            params
+                .into_iter()
+                .filter_map(|(key, value)| Some((key, value?))),
+        );
+        Some(url)
+    }
+
+    // Universal links, e.g. https://opencode.ai/s/<id>
+    fn from_web_url(url: &Url) -> Option<Self> {
+        let host = url.host_str()?;
+        if !WEB_HOSTS.iter().any(|v| host.eq_ignore_ascii_case(v)) {
+            return None;
+        }
+
+        let mut segments = url.path_segments()?.filter(|v| !v.is_empty());
+        match (segments.next()?, segments.next(), segments.next()) {
+            ("s", Some(id), None) => Some(Self::OpenShare {
+                id: id.to_string(),
+                url: url.to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let name_ok = |v: &str| {
+            !v.is_empty()
+                && v.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        };
+
+        match self {
+            Self::NewSession {
+                prompt,
+                model,
+                agent,
+                ..
+            } => {
+                let prompt_ok = prompt.as_ref().is_none_or(|v| v.len() <= MAX_PROMPT_LEN);
+                // Models are addressed as "<provider>/<model>".
+                let model_ok = model.as_ref().is_none_or(|v| {
+                    v.split_once('/')
+                        .is_some_and(|(provider, model)| !provider.is_empty() && !model.is_empty())
+                });
+                let agent_ok = agent.as_deref().is_none_or(name_ok);
+
+                prompt_ok && model_ok && agent_ok
+            }
+            Self::RunCommand { command, args, .. } => {
+                name_ok(command) && args.as_ref().is_none_or(|v| v.len() <= MAX_PROMPT_LEN)
+            }
+            _ => true,
+        }
+    }
+
+    /// Stable name used to key persisted policy decisions.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::OpenProject { .. } => "open_project",
+            Self::OpenShare { .. } => "open_share",
+            Self::NewSession { .. } => "new_session",
+            Self::OpenFile { .. } => "open_file",
+            Self::AuthCallback { .. } => "auth_callback",
+            Self::RunCommand { .. } => "run_command",
+        }
+    }
+
+    /// Any website can trigger opencode:// URLs. Actions that act on the user's
+    /// behalf (e.g. submit a prompt) are risky; navigation is not.
+    pub fn risk(&self) -> Risk {
+        match self {
+            Self::NewSession {
+                prompt: Some(_), ..
+            }
+            | Self::RunCommand { .. } => Risk::High,
+            _ => Risk::Low,
+        }
+    }
+}
+
+/// Where the session behind an opencode.ai share link lives.
+#[derive(serde::Serialize, specta::Type, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ShareTarget {
+    /// The shared session is on a server the app is connected to, local or
+    /// remote, and can be opened in place.
+    Session {
+        server: String,
+        directory: String,
+        session: String,
+    },
+    /// Someone else's share, which only the web view can show.
+    Web { url: String },
+}
+
+#[derive(serde::Deserialize)]
+struct SessionInfo {
+    id: String,
+    directory: String,
+    share: Option<ShareInfo>,
+}
+
+#[derive(serde::Deserialize)]
+struct ShareInfo {
+    url: String,
+}
+
+fn share_id(url: &str) -> Option<String> {
+    match DeepLinkAction::from_url(&Url::parse(url).ok()?)? {
+        DeepLinkAction::OpenShare { id, .. } => Some(id),
+        _ => None,
+    }
+}
+
+async fn list_sessions(
+    server: &str,
+    password: Option<&str>,
+    directory: Option<&str>,
+) -> Result<Vec<SessionInfo>, String> {
+    let mut url = Url::parse(server)
+        .and_then(|v| v.join("/session"))
+        .map_err(|e| format!("Invalid server URL: {}", e))?;
+    url.query_pairs_mut().append_pair("limit", "1000");
+    if let Some(directory) = directory {
+        url.query_pairs_mut().append_pair("directory", directory);
+    }
+
+    let client = crate::proxy::client_builder(&url)
+        .timeout(SHARE_LOOKUP_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let mut req = client.get(url);
+    if let Some(password) = password {
+        req = req.basic_auth("opencode", Some(password));
+    }
+
+    let body = req
+        .send()
+        .await
+        .and_then(|v| v.error_for_status())
+        .map_err(|e| format!("Failed to list sessions: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read sessions: {}", e))?;
+    serde_json::from_str(&body).map_err(|e| format!("Failed to parse sessions: {}", e))
+}
+
+/// Maps an opencode.ai share link to the session it was made from when that
+/// session is on one of the connected servers. Sessions are listed per
+/// project, so each server is asked about every recent local project.
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_share_link(app: AppHandle, url: String) -> Result<ShareTarget, String> {
+    let id = share_id(&url).ok_or_else(|| format!("Not a share link: {url}"))?;
+
+    let directories = crate::projects::load(&app)?
+        .into_iter()
+        .filter(|v| v.host.is_none())
+        .map(|v| Some(v.path))
+        .chain([None])
+        .collect::<Vec<_>>();
+    let servers = app.state::<crate::health::HealthMonitor>().servers();
+
+    for (server, password) in servers {
+        for directory in &directories {
+            let sessions =
+                match list_sessions(&server, password.as_deref(), directory.as_deref()).await {
+                    Ok(sessions) => sessions,
+                    Err(e) => {
+                        tracing::debug!(%server, "Skipping server for share lookup: {e}");
+                        break;
+                    }
+                };
+
+            let found = sessions.into_iter().find(|v| {
+                v.share
+                    .as_ref()
+                    .and_then(|share| share_id(&share.url))
+                    .is_some_and(|v| v == id)
+            });
+            if let Some(session) = found {
+                return Ok(ShareTarget::Session {
+                    server,
+                    directory: session.directory,
+                    session: session.id,
+                });
+            }
+        }
+    }
+
+    Ok(ShareTarget::Web { url })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Risk {
+    Low,
+    High,
+}
+
+/// All actions delivered by a single OS activation, in the order the OS
+/// supplied them. macOS can hand over several URLs at once, and `seq` lets the
+/// frontend detect batches it has already handled.
+#[derive(tauri_specta::Event, serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+pub struct DeepLinkBatch {
+    pub seq: u32,
+    pub actions: Vec<DeepLinkAction>,
+}
+
+static SEQ: AtomicU32 = AtomicU32::new(0);
+
+impl DeepLinkBatch {
+    pub fn new(actions: Vec<DeepLinkAction>) -> Option<Self> {
+        if actions.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            seq: SEQ.fetch_add(1, Ordering::Relaxed),
+            actions,
+        })
+    }
+}
+
+/// Emitted instead of a batch for actions that need the user's consent. The
+/// frontend answers with `confirm_deep_link`.
+#[derive(tauri_specta::Event, serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+pub struct DeepLinkConfirmation {
+    pub id: String,
+    pub action: DeepLinkAction,
+}
+
+/// Everything dispatched before the frontend subscribed, in arrival order.
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, Default)]
+pub struct PendingDeepLinks {
+    pub batches: Vec<DeepLinkBatch>,
+    pub confirmations: Vec<DeepLinkConfirmation>,
+}
+
+/// Deep links that arrive before the webview is listening (e.g. the URL that
+/// cold-started the app) would be lost as events, so they are held here until
+/// the frontend calls `drain_pending_deep_links`. `None` once drained.
+struct Queue(Mutex<Option<PendingDeepLinks>>);
+
+impl Queue {
+    /// Holds `item` while the frontend isn't ready, otherwise hands it back to
+    /// be emitted.
+    fn hold<T>(
+        &self,
+        item: T,
+        select: impl FnOnce(&mut PendingDeepLinks) -> &mut Vec<T>,
+    ) -> Option<T> {
+        match self.0.lock().unwrap().as_mut() {
+            Some(pending) => {
+                select(pending).push(item);
+                None
+            }
+            None => Some(item),
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn drain_pending_deep_links(app: AppHandle) -> PendingDeepLinks {
+    app.state::<Queue>()
+        .0
+        .lock()
+        .unwrap()
+        .take()
+        .unwrap_or_default()
+}
+
+#[derive(Default)]
+struct PendingConfirmations(Mutex<HashMap<String, DeepLinkAction>>);
+
+/// CSRF `state` values handed out for provider logins that redirect back to
+/// opencode://auth/callback. Each value is single use and expires.
+#[derive(Default)]
+struct AuthStates(Mutex<HashMap<String, (String, Instant)>>);
+
+impl AuthStates {
+    fn issue(&self, provider: &str, now: Instant) -> String {
+        let state = uuid::Uuid::new_v4().simple().to_string();
+        let mut states = self.0.lock().unwrap();
+        states.retain(|_, (_, issued)| now.duration_since(*issued) < AUTH_STATE_TTL);
+        states.insert(state.clone(), (provider.to_string(), now));
+        state
+    }
+
+    fn verify(&self, provider: &str, state: &str, now: Instant) -> bool {
+        self.0
+            .lock()
+            .unwrap()
+            .remove(state)
+            .is_some_and(|(expected, issued)| {
+                expected == provider && now.duration_since(issued) < AUTH_STATE_TTL
+            })
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn create_auth_state(app: AppHandle, provider: String) -> String {
+    app.state::<AuthStates>().issue(&provider, Instant::now())
+}
+
+pub fn parse_urls(urls: &[Url]) -> Vec<DeepLinkAction> {
+    urls.iter()
+        .filter_map(|url| {
+            let action = DeepLinkAction::from_url(url);
+            if action.is_none() {
+                tracing::debug!(%url, "Ignoring unrecognised or invalid deep link");
+            }
+            action
+        })
+        .collect()
+}
+
+/// Picks the paths out of a command line such as `OpenCode open .`: the
+/// optional `open` subcommand, flags and URLs (handled by the deep link plugin)
+/// are skipped.
+fn path_args(args: &[String]) -> Vec<&str> {
+    let mut args = args.iter().skip(1).map(String::as_str).peekable();
+    args.next_if_eq(&"open");
+    args.filter(|v| !v.starts_with('-') && !v.contains("://"))
+        .collect()
+}
+
+/// Turns directories passed on the command line into OpenProject actions,
+/// resolving relative paths against the invoking shell's `cwd`.
+pub fn parse_args(args: &[String], cwd: &str) -> Vec<DeepLinkAction> {
+    path_args(args)
+        .into_iter()
+        .filter_map(|arg| {
+            let directory = std::fs::canonicalize(Path::new(cwd).join(arg))
+                .ok()
+                .filter(|v| v.is_dir());
+            if directory.is_none() {
+                tracing::debug!(%arg, "Ignoring argument that is not a directory");
+            }
+            directory
+        })
+        .map(|directory| DeepLinkAction::OpenProject {
+            directory: directory.to_string_lossy().to_string(),
+            session: None,
+        })
+        .collect()
+}
+
+/// Opens directories from a command line, either our own or one forwarded by
+/// a second instance.
+pub fn open_args(app: &AppHandle, args: &[String], cwd: &str) {
+    if app.try_state::<Queue>().is_none() {
+        tracing::warn!("Dropping command line arguments received before startup");
+        return;
+    }
+
+    dispatch(app, parse_args(args, cwd));
+}
+
+/// Resolves `path` against the project `directory`, rejecting anything that
+/// would end up outside of it. Works lexically while parsing; `confine` checks
+/// the result against the disk before the link is acted on.
+fn resolve_in(directory: &str, path: &str) -> Option<String> {
+    let root = Path::new(directory);
+    if !root.is_absolute() {
+        return None;
+    }
+
+    let mut resolved = PathBuf::new();
+    for component in root.join(path).components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            other => resolved.push(other),
+        }
+    }
+
+    resolved
+        .starts_with(root)
+        .then(|| resolved.to_string_lossy().to_string())
+}
+
+// The canonical root and file, or `None` if the file resolves outside the
+// root, e.g. through a symlink.
+fn canonical_in(directory: &str, path: &str) -> Option<(String, String)> {
+    let directory = crate::server::canonical_directory(directory).ok()?;
+    let path = std::fs::canonicalize(path)
+        .ok()
+        .filter(|v| v.starts_with(&directory))?;
+    Some((directory, path.to_string_lossy().to_string()))
+}
+
+/// Canonicalizes an OpenFile link so a symlink can't lead out of its root, and
+/// reports whether that root is a project the user already opened or
+/// trusted. The link picks its own `directory`, so an unknown root is no
+/// confinement at all. `None` drops the link. Other actions pass unchanged.
+fn confine(app: &AppHandle, action: DeepLinkAction) -> Option<(DeepLinkAction, bool)> {
+    let DeepLinkAction::OpenFile {
+        directory,
+        path,
+        line,
+        column,
+        session,
+    } = action
+    else {
+        return Some((action, true));
+    };
+
+    let (directory, path) = canonical_in(&directory, &path)?;
+    let location = crate::projects::ProjectLocation {
+        path: directory.clone(),
+        host: None,
+    };
+    let known =
+        crate::trust::require(app, &location).is_ok() || crate::projects::contains(app, &location);
+
+    Some((
+        DeepLinkAction::OpenFile {
+            directory,
+            path,
+            line,
+            column,
+            session,
+        },
+        known,
+    ))
+}
+
+fn query(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+        .filter(|v| !v.is_empty())
+}
+
+/// Builds a shareable "open this in opencode" link for a project, optionally
+/// pointing at a session, and copies it to the clipboard if asked to.
+#[tauri::command]
+#[specta::specta]
+pub fn create_deep_link(
+    app: AppHandle,
+    directory: String,
+    session: Option<String>,
+    copy: bool,
+) -> Result<String, String> {
+    let url = DeepLinkAction::OpenProject { directory, session }
+        .to_url()
+        .ok_or("Failed to build deep link")?
+        .to_string();
+
+    if copy {
+        app.clipboard()
+            .write_text(url.clone())
+            .map_err(|e| format!("Failed to copy deep link: {}", e))?;
+    }
+
+    Ok(url)
+}
+
+pub fn setup(app: &AppHandle) {
+    app.manage(PendingConfirmations::default());
+    app.manage(AuthStates::default());
+    app.manage(Queue(Mutex::new(Some(PendingDeepLinks::default()))));
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        dispatch(&handle, parse_urls(&event.urls()));
+    });
+
+    // URLs the app was launched with
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        dispatch(app, parse_urls(&urls));
+    }
+
+    // Directories the app was launched with
+    if let Ok(cwd) = std::env::current_dir() {
+        let args = std::env::args().collect::<Vec<_>>();
+        open_args(app, &args, &cwd.to_string_lossy());
+    }
+}
+
+fn dispatch(app: &AppHandle, actions: Vec<DeepLinkAction>) {
+    let policy = policy::load(app);
+
+    let (confirm, actions): (Vec<_>, Vec<_>) = actions
+        .into_iter()
+        .filter(|action| match action {
+            DeepLinkAction::AuthCallback {
+                provider, state, ..
+            } => {
+                let valid = app
+                    .state::<AuthStates>()
+                    .verify(provider, state, Instant::now());
+                if !valid {
+                    tracing::warn!(%provider, "Dropping auth callback with unknown or expired state");
+                }
+                valid
+            }
+            _ => true,
+        })
+        .filter_map(|action| {
+            let kind = action.kind();
+            let confined = confine(app, action);
+            if confined.is_none() {
+                tracing::warn!(kind, "Dropping deep link to a file outside its project");
+            }
+            confined
+        })
+        .map(|(action, known_root)| {
+            // Saying "always allow" once doesn't extend to roots the app
+            // hasn't seen.
+            let decision = match policy::decide(&policy, &action) {
+                policy::Decision::Allow if !known_root => policy::Decision::Ask,
+                decision => decision,
+            };
+            (action, decision)
+        })
+        .filter(|(action, decision)| {
+            let allowed = *decision != policy::Decision::Deny;
+            if !allowed {
+                tracing::info!(kind = action.kind(), "Deep link denied by policy");
+            }
+            allowed
+        })
+        .partition(|(_, decision)| *decision == policy::Decision::Ask);
+
+    for (action, _) in confirm {
+        let id = uuid::Uuid::new_v4().to_string();
+        tracing::info!(%id, "Deep link needs confirmation");
+
+        app.state::<PendingConfirmations>()
+            .0
+            .lock()
+            .unwrap()
+            .insert(id.clone(), action.clone());
+        let confirmation = DeepLinkConfirmation { id, action };
+        if let Some(confirmation) = app
+            .state::<Queue>()
+            .hold(confirmation, |v| &mut v.confirmations)
+        {
+            let _ = confirmation.emit(app);
+        }
+    }
+
+    let actions = actions.into_iter().map(|(action, _)| action).collect();
+    if let Some(batch) = DeepLinkBatch::new(actions) {
+        // Actions can carry auth codes and prompts, so only their count is logged.
+        tracing::info!(
+            seq = batch.seq,
+            count = batch.actions.len(),
+            "Dispatching deep links"
+        );
+        if let Some(batch) = app.state::<Queue>().hold(batch, |v| &mut v.batches) {
+            let _ = batch.emit(app);
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn confirm_deep_link(
+    app: AppHandle,
+    id: String,
+    accept: bool,
+    remember: bool,
+) -> Result<(), String> {
+    let action = app
+        .state::<PendingConfirmations>()
+        .0
+        .lock()
+        .unwrap()
+        .remove(&id)
+        .ok_or_else(|| format!("Unknown deep link confirmation: {id}"))?;
+
+    tracing::info!(%id, accept, remember, "Deep link confirmation answered");
+
+    if remember {
+        let decision = if accept {
+            policy::Decision::Allow
+        } else {
+            policy::Decision::Deny
+        };
+        policy::set_deep_link_policy(app.clone(), action.kind().to_string(), Some(decision))?;
+    }
+
+    if accept && let Some(batch) = DeepLinkBatch::new(vec![action]) {
+        batch
+            .emit(&app)
+            .map_err(|e| format!("Failed to dispatch deep link: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Per-action-kind decisions that override the risk based default. Custom
+/// scheme activations carry no origin, so the action kind is the finest
+/// granularity the OS lets us key on.
+pub mod policy {
+    use super::*;
+    use tauri_plugin_store::StoreExt;
+
+    use crate::constants::{DEEP_LINK_POLICY_KEY, SETTINGS_STORE};
+
+    #[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Copy, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Decision {
+        Allow,
+        Ask,
+        Deny,
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, PartialEq)]
+    pub struct PolicyEntry {
+        pub kind: String,
+        pub decision: Decision,
+    }
+
+    pub type Policy = HashMap<String, Decision>;
+
+    pub fn decide(policy: &Policy, action: &DeepLinkAction) -> Decision {
+        if let Some(decision) = policy.get(action.kind()) {
+            return *decision;
+        }
+
+        match action.risk() {
+            Risk::Low => Decision::Allow,
+            Risk::High => Decision::Ask,
+        }
+    }
+
+    pub fn load(app: &AppHandle) -> Policy {
+        app.store(SETTINGS_STORE)
+            .ok()
+            .and_then(|store| store.get(DEEP_LINK_POLICY_KEY))
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+
+    #[tauri::command]
+    #[specta::specta]
+    pub fn get_deep_link_policy(app: AppHandle) -> Vec<PolicyEntry> {
+        let mut entries = load(&app)
+            .into_iter()
+            .map(|(kind, decision)| PolicyEntry { kind, decision })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.kind.cmp(&b.kind));
+        entries
+    }
+
+    /// Sets (or with `None`, resets to the default) the decision for an action kind.
+    #[tauri::command]
+    #[specta::specta]
+    pub fn set_deep_link_policy(
+        app: AppHandle,
+        kind: String,
+        decision: Option<Decision>,
+    ) -> Result<(), String> {
+        let store = app
+            .store(SETTINGS_STORE)
+            .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+        let mut policy = load(&app);
+        match decision {
+            Some(decision) => policy.insert(kind, decision),
+            None => policy.remove(&kind),
+        };
+
+        store.set(DEEP_LINK_POLICY_KEY, serde_json::json!(policy));
+        store
+            .save()
+            .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Option<DeepLinkAction> {
+        DeepLinkAction::from_url(&Url::parse(input).unwrap())
+    }
+
+    #[test]
+    fn parses_open_project() {
+        assert_eq!(
+            parse("opencode://open-project?directory=/tmp/demo"),
+            Some(DeepLinkAction::OpenProject {
+                directory: "/tmp/demo".to_string(),
+                session: None,
+            })
+        );
+    }
+
+    #[test]
+    fn to_url_round_trips_with_encoding() {
+        let actions = [
+            DeepLinkAction::OpenProject {
+                directory: "/tmp/my project & co".to_string(),
+                session: Some("ses_1".to_string()),
+            },
+            DeepLinkAction::NewSession {
+                directory: "/tmp/demo".to_string(),
+                prompt: Some("fix #12 & ship?".to_string()),
+                model: Some("anthropic/claude".to_string()),
+                agent: None,
+            },
+        ];
+
+        for action in actions {
+            let url = action.to_url().unwrap();
+            assert_eq!(DeepLinkAction::from_url(&url), Some(action));
+        }
+    }
+
+    #[test]
+    fn to_url_is_canonical() {
+        let action = DeepLinkAction::OpenProject {
+            directory: "/tmp/a b".to_string(),
+            session: None,
+        };
+        assert_eq!(
+            action.to_url().unwrap().as_str(),
+            "opencode://open-project?directory=%2Ftmp%2Fa+b"
+        );
+    }
+
+    #[test]
+    fn ignores_open_project_without_directory() {
+        assert_eq!(parse("opencode://open-project"), None);
+        assert_eq!(parse("opencode://open-project?directory="), None);
+    }
+
+    #[test]
+    fn parses_share_universal_link() {
+        assert_eq!(
+            parse("https://opencode.ai/s/abc123"),
+            Some(DeepLinkAction::OpenShare {
+                id: "abc123".to_string(),
+                url: "https://opencode.ai/s/abc123".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_urls_keeps_order_and_skips_unknown_links() {
+        let urls = [
+            "opencode://open-project?directory=/a",
+            "opencode://unknown",
+            "opencode://open-project?directory=/b",
+        ]
+        .map(|v| Url::parse(v).unwrap());
+
+        assert_eq!(
+            parse_urls(&urls),
+            ["/a", "/b"].map(|directory| DeepLinkAction::OpenProject {
+                directory: directory.to_string(),
+                session: None,
+            })
+        );
+    }
+
+    #[test]
+    fn path_args_skip_binary_subcommand_flags_and_urls() {
+        let args = |v: &[&str]| v.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            path_args(&args(&["OpenCode", "open", ".", "../other"])),
+            [".", "../other"]
+        );
+        assert_eq!(
+            path_args(&args(&[
+                "OpenCode",
+                "--autostart",
+                "opencode://open-project?directory=/a",
+                "/b"
+            ])),
+            ["/b"]
+        );
+        assert!(path_args(&args(&["OpenCode"])).is_empty());
+    }
+
+    #[test]
+    fn parse_args_resolves_directories_against_cwd() {
+        let dir = std::env::temp_dir().join(format!("opencode-args-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("project")).unwrap();
+        std::fs::write(dir.join("file.txt"), "").unwrap();
+
+        let args = ["OpenCode", "project", "file.txt", "missing"].map(String::from);
+        let expected = std::fs::canonicalize(dir.join("project")).unwrap();
+
+        assert_eq!(
+            parse_args(&args, &dir.to_string_lossy()),
+            [DeepLinkAction::OpenProject {
+                directory: expected.to_string_lossy().to_string(),
+                session: None,
+            }]
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn extracts_share_ids() {
+        assert_eq!(
+            share_id("https://opencode.ai/s/abc123"),
+            Some("abc123".to_string())
+        );
+        assert_eq!(share_id("https://example.com/s/abc123"), None);
+        assert_eq!(share_id("opencode://open-project?directory=/a"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn canonical_in_rejects_symlinks_out_of_the_root() {
+        let dir = std::env::temp_dir().join(format!("opencode-confine-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("project")).unwrap();
+        std::fs::write(dir.join("project/main.rs"), "").unwrap();
+        std::fs::write(dir.join("secret"), "").unwrap();
+        std::os::unix::fs::symlink(dir.join("secret"), dir.join("project/link")).unwrap();
+
+        let root = dir.join("project").to_string_lossy().to_string();
+        let file = |name: &str| dir.join("project").join(name).to_string_lossy().to_string();
+
+        let (directory, path) = canonical_in(&root, &file("main.rs")).unwrap();
+        assert!(Path::new(&path).starts_with(&directory));
+        assert_eq!(canonical_in(&root, &file("link")), None);
+        assert_eq!(canonical_in(&root, &file("missing")), None);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn batches_are_sequenced_and_never_empty() {
+        let action = DeepLinkAction::OpenProject {
+            directory: "/a".to_string(),
+            session: None,
+        };
+
+        let first = DeepLinkBatch::new(vec![action.clone()]).unwrap();
+        let second = DeepLinkBatch::new(vec![action]).unwrap();
+        assert!(second.seq > first.seq);
+
+        assert!(DeepLinkBatch::new(vec![]).is_none());
+    }
+
+    #[test]
+    fn parses_new_session() {
+        assert_eq!(
+            parse(
+                "opencode://new-session?directory=/tmp/demo&prompt=fix%20the%20bug&model=anthropic/claude&agent=build"
+            ),
+            Some(DeepLinkAction::NewSession {
+                directory: "/tmp/demo".to_string(),
+                prompt: Some("fix the bug".to_string()),
+                model: Some("anthropic/claude".to_string()),
+                agent: Some("build".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_new_session() {
+        assert_eq!(parse("opencode://new-session?prompt=hi"), None);
+        assert_eq!(
+            parse("opencode://new-session?directory=/a&model=claude"),
+            None
+        );
+        assert_eq!(
+            parse("opencode://new-session?directory=/a&agent=rm%20-rf"),
+            None
+        );
+
+        let long = "a".repeat(MAX_PROMPT_LEN + 1);
+        assert_eq!(
+            parse(&format!(
+                "opencode://new-session?directory=/a&prompt={long}"
+            )),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parses_open_file_relative_to_project() {
+        assert_eq!(
+            parse(
+                "opencode://open-file?directory=/repo&path=src/main.rs&line=12&column=4&session=ses_1"
+            ),
+            Some(DeepLinkAction::OpenFile {
+                directory: "/repo".to_string(),
+                path: "/repo/src/main.rs".to_string(),
+                line: Some(12),
+                column: Some(4),
+                session: Some("ses_1".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn open_file_accepts_absolute_paths_inside_project() {
+        assert_eq!(
+            parse("opencode://open-file?directory=/repo&path=/repo/./a/../b.rs"),
+            Some(DeepLinkAction::OpenFile {
+                directory: "/repo".to_string(),
+                path: "/repo/b.rs".to_string(),
+                line: None,
+                column: None,
+                session: None,
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn open_file_rejects_paths_outside_project() {
+        assert_eq!(
+            parse("opencode://open-file?directory=/repo&path=../etc/passwd"),
+            None
+        );
+        assert_eq!(
+            parse("opencode://open-file?directory=/repo&path=/etc/passwd"),
+            None
+        );
+        assert_eq!(parse("opencode://open-file?directory=repo&path=a.rs"), None);
+        assert_eq!(parse("opencode://open-file?path=/repo/a.rs"), None);
+    }
+
+    #[test]
+    fn parses_auth_callback() {
+        assert_eq!(
+            parse("opencode://auth/callback?provider=github&code=abc&state=xyz"),
+            Some(DeepLinkAction::AuthCallback {
+                provider: "github".to_string(),
+                code: "abc".to_string(),
+                state: "xyz".to_string(),
+            })
+        );
+        assert_eq!(
+            parse("opencode://auth/callback?provider=github&code=abc"),
+            None
+        );
+        assert_eq!(
+            parse("opencode://auth/other?provider=github&code=abc&state=xyz"),
+            None
+        );
+    }
+
+    #[test]
+    fn auth_states_are_single_use_and_provider_bound() {
+        let states = AuthStates::default();
+        let now = Instant::now();
+
+        let state = states.issue("github", now);
+        assert!(!states.verify("gitlab", &state, now));
+        // A failed attempt still consumes the state.
+        assert!(!states.verify("github", &state, now));
+
+        let state = states.issue("github", now);
+        assert!(states.verify("github", &state, now));
+        assert!(!states.verify("github", &state, now));
+    }
+
+    #[test]
+    fn auth_states_expire() {
+        let states = AuthStates::default();
+        let now = Instant::now();
+
+        let state = states.issue("github", now);
+        assert!(!states.verify("github", &state, now + AUTH_STATE_TTL));
+    }
+
+    #[test]
+    fn policy_defaults_follow_risk() {
+        let session = |prompt: Option<&str>| DeepLinkAction::NewSession {
+            directory: "/a".to_string(),
+            prompt: prompt.map(str::to_string),
+            model: None,
+            agent: None,
+        };
+        let policy = policy::Policy::new();
+
+        assert_eq!(
+            policy::decide(&policy, &session(Some("hi"))),
+            policy::Decision::Ask
+        );
+        assert_eq!(
+            policy::decide(&policy, &session(None)),
+            policy::Decision::Allow
+        );
+    }
+
+    #[test]
+    fn parses_run_command_and_requires_confirmation() {
+        let action = parse("opencode://run?command=review&args=HEAD~1&directory=/repo").unwrap();
+        assert_eq!(
+            action,
+            DeepLinkAction::RunCommand {
+                command: "review".to_string(),
+                args: Some("HEAD~1".to_string()),
+                directory: "/repo".to_string(),
+            }
+        );
+        assert_eq!(
+            policy::decide(&policy::Policy::new(), &action),
+            policy::Decision::Ask
+        );
+        assert_eq!(
+            DeepLinkAction::from_url(&action.to_url().unwrap()),
+            Some(action)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_run_command() {
+        assert_eq!(parse("opencode://run?command=review"), None);
+        assert_eq!(parse("opencode://run?directory=/repo"), None);
+        assert_eq!(
+            parse("opencode://run?command=rm%20-rf&directory=/repo"),
+            None
+        );
+    }
+
+    #[test]
+    fn policy_decisions_override_defaults() {
+        let project = DeepLinkAction::OpenProject {
+            directory: "/a".to_string(),
+            session: None,
+        };
+        let policy = policy::Policy::from([("open_project".to_string(), policy::Decision::Deny)]);
+
+        assert_eq!(policy::decide(&policy, &project), policy::Decision::Deny);
+    }
+
+    #[test]
+    fn ignores_other_web_links() {
+        assert_eq!(parse("https://opencode.ai/docs"), None);
+        assert_eq!(parse("https://opencode.ai/s/abc/extra"), None);
+        assert_eq!(parse("https://example.com/s/abc123"), None);
+        assert_eq!(parse("http://opencode.ai/s/abc123"), None);
+    }
+}
@@ -0,0 +1,363 @@
+use std::{path::Path, process::Command};
+use tauri::{AppHandle, Manager};
+
+use crate::{health::HealthMonitor, privacy, proxy, server};
+
+const LOW_DISK_BYTES: u64 = 1024 * 1024 * 1024;
+const CRITICAL_DISK_BYTES: u64 = 100 * 1024 * 1024;
+
+#[derive(serde::Serialize, specta::Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+    Skipped,
+}
+
+#[derive(serde::Serialize, specta::Type, Clone, Debug)]
+pub struct DiagnosticCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl DiagnosticCheck {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Environment report for the diagnostics screen and bug reports. Details pass
+/// through the privacy scrubber, so they are safe to attach when privacy mode
+/// is on.
+#[derive(serde::Serialize, specta::Type, Clone, Debug)]
+pub struct DiagnosticsReport {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub generated_at: String,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn run_diagnostics(app: AppHandle) -> Result<DiagnosticsReport, String> {
+    let dirs = [
+        ("temp", Some(std::env::temp_dir())),
+        ("app data", app.path().app_data_dir().ok()),
+        ("app local data", app.path().app_local_data_dir().ok()),
+        ("logs", app.path().app_log_dir().ok()),
+    ];
+
+    let mut checks = tokio::task::spawn_blocking(move || {
+        let mut checks = vec![
+            check_ssh(),
+            check_webview(),
+            check_proxy(),
+            check_keychain(),
+        ];
+        checks.extend(
+            dirs.iter()
+                .map(|(name, dir)| check_disk(name, dir.as_deref())),
+        );
+        checks
+    })
+    .await
+    .map_err(|e| format!("Failed to run diagnostics: {}", e))?;
+
+    checks.extend(check_servers(&app).await);
+
+    for check in &mut checks {
+        check.detail = privacy::scrub(&check.detail);
+    }
+
+    Ok(DiagnosticsReport {
+        app_version: app.package_info().version.to_string(),
+        os: format!(
+            "{} {}",
+            tauri_plugin_os::platform(),
+            tauri_plugin_os::version()
+        ),
+        arch: tauri_plugin_os::arch().to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        checks,
+    })
+}
+
+fn output(program: &str, args: &[&str]) -> Result<std::process::Output, String> {
+    Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {program}: {e}"))
+}
+
+fn check_ssh() -> DiagnosticCheck {
+    const NAME: &str = "ssh";
+
+    // `ssh -V` prints its version to stderr.
+    match output("ssh", &["-V"]) {
+        Ok(out) if out.status.success() => {
+            let version = String::from_utf8_lossy(&out.stderr).trim().to_string();
+            DiagnosticCheck::new(NAME, CheckStatus::Ok, version)
+        }
+        Ok(out) => DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Warn,
+            String::from_utf8_lossy(&out.stderr).trim().to_string(),
+        ),
+        Err(e) => DiagnosticCheck::new(NAME, CheckStatus::Warn, e),
+    }
+}
+
+fn check_webview() -> DiagnosticCheck {
+    const NAME: &str = "webview";
+
+    match tauri::webview_version() {
+        Ok(version) => DiagnosticCheck::new(NAME, CheckStatus::Ok, version),
+        Err(e) => DiagnosticCheck::new(NAME, CheckStatus::Fail, e.to_string()),
+    }
+}
+
+fn check_proxy() -> DiagnosticCheck {
+    const NAME: &str = "proxy";
+
+    let Some(proxy) = proxy::current() else {
+        return DiagnosticCheck::new(NAME, CheckStatus::Ok, "No proxy configured");
+    };
+
+    if let Err(e) = reqwest::Proxy::all(&proxy) {
+        return DiagnosticCheck::new(NAME, CheckStatus::Fail, format!("Invalid proxy: {e}"));
+    }
+    let proxy = proxy::redact(&proxy);
+
+    let no_proxy = proxy::no_proxy();
+
+    if excludes_loopback(&no_proxy) {
+        DiagnosticCheck::new(NAME, CheckStatus::Ok, format!("Using {proxy}"))
+    } else {
+        DiagnosticCheck::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("Using {proxy}, but NO_PROXY does not exclude localhost"),
+        )
+    }
+}
+
+fn excludes_loopback(no_proxy: &str) -> bool {
+    no_proxy
+        .split(',')
+        .map(str::trim)
+        .any(|v| matches!(v, "*" | "localhost" | "127.0.0.1" | "::1"))
+}
+
+#[cfg(target_os = "macos")]
+fn check_keychain() -> DiagnosticCheck {
+    match output("security", &["default-keychain"]) {
+        Ok(out) if out.status.success() => {
+            DiagnosticCheck::new("keychain", CheckStatus::Ok, "Default keychain available")
+        }
+        Ok(_) => DiagnosticCheck::new("keychain", CheckStatus::Warn, "No default keychain"),
+        Err(e) => DiagnosticCheck::new("keychain", CheckStatus::Warn, e),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn check_keychain() -> DiagnosticCheck {
+    const SECRETS: &str = "org.freedesktop.secrets";
+
+    // Secret Service providers may be running or only D-Bus activatable.
+    let available = ["ListNames", "ListActivatableNames"].iter().any(|method| {
+        output(
+            "dbus-send",
+            &[
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.DBus",
+                "/org/freedesktop/DBus",
+                &format!("org.freedesktop.DBus.{method}"),
+            ],
+        )
+        .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).contains(SECRETS))
+    });
+
+    if available {
+        DiagnosticCheck::new("keychain", CheckStatus::Ok, "Secret Service available")
+    } else {
+        DiagnosticCheck::new(
+            "keychain",
+            CheckStatus::Warn,
+            "No Secret Service provider found",
+        )
+    }
+}
+
+// `cmdkey /list` fails when the Credential Manager service isn't running.
+#[cfg(windows)]
+fn check_keychain() -> DiagnosticCheck {
+    match output("cmdkey", &["/list"]) {
+        Ok(out) if out.status.success() => {
+            DiagnosticCheck::new("keychain", CheckStatus::Ok, "Credential Manager available")
+        }
+        Ok(_) => DiagnosticCheck::new(
+            "keychain",
+            CheckStatus::Warn,
+            "Credential Manager unavailable",
+        ),
+        Err(e) => DiagnosticCheck::new("keychain", CheckStatus::Skipped, e),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn check_keychain() -> DiagnosticCheck {
+    DiagnosticCheck::new("keychain", CheckStatus::Skipped, "Unsupported platform")
+}
+
+fn check_disk(name: &str, dir: Option<&Path>) -> DiagnosticCheck {
+    let name = format!("disk ({name})");
+
+    let Some(dir) = dir else {
+        return DiagnosticCheck::new(&name, CheckStatus::Skipped, "Directory unavailable");
+    };
+
+    let Some(available) = available_space(dir) else {
+        return DiagnosticCheck::new(
+            &name,
+            CheckStatus::Skipped,
+            format!("Could not determine free space for {}", dir.display()),
+        );
+    };
+
+    let status = if available < CRITICAL_DISK_BYTES {
+        CheckStatus::Fail
+    } else if available < LOW_DISK_BYTES {
+        CheckStatus::Warn
+    } else {
+        CheckStatus::Ok
+    };
+
+    DiagnosticCheck::new(
+        &name,
+        status,
+        format!(
+            "{} MiB free at {}",
+            available / (1024 * 1024),
+            dir.display()
+        ),
+    )
+}
+
+// The directory may not exist yet, so measure the closest existing ancestor.
+fn existing_ancestor(dir: &Path) -> Option<&Path> {
+    dir.ancestors().find(|v| v.exists())
+}
+
+#[cfg(unix)]
+fn available_space(dir: &Path) -> Option<u64> {
+    let dir = existing_ancestor(dir)?;
+    let out = output("df", &["-Pk", &dir.to_string_lossy()]).ok()?;
+    parse_df_available(&String::from_utf8_lossy(&out.stdout))
+}
+
+#[cfg(windows)]
+fn available_space(dir: &Path) -> Option<u64> {
+    use std::path::Component;
+
+    let dir = existing_ancestor(dir)?;
+    let Some(Component::Prefix(prefix)) = dir.components().next() else {
+        return None;
+    };
+    let drive = prefix
+        .as_os_str()
+        .to_string_lossy()
+        .trim_end_matches(':')
+        .to_string();
+
+    let out = output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            &format!("(Get-PSDrive -Name '{drive}').Free"),
+        ],
+    )
+    .ok()?;
+    String::from_utf8_lossy(&out.stdout).trim().parse().ok()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn available_space(_dir: &Path) -> Option<u64> {
+    None
+}
+
+// POSIX `df -Pk` output: a header, then "<fs> <blocks> <used> <available> <capacity> <mount>".
+#[cfg_attr(not(unix), allow(dead_code))]
+fn parse_df_available(output: &str) -> Option<u64> {
+    let kib = output
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+async fn check_servers(app: &AppHandle) -> Vec<DiagnosticCheck> {
+    let servers = app
+        .try_state::<HealthMonitor>()
+        .map(|v| v.servers())
+        .unwrap_or_default();
+
+    if servers.is_empty() {
+        return vec![DiagnosticCheck::new(
+            "server",
+            CheckStatus::Warn,
+            "No server connected",
+        )];
+    }
+
+    let mut checks = Vec::new();
+    for (url, password) in servers {
+        let name = format!("server ({url})");
+        let check = match server::fetch_health(&url, password.as_deref()).await {
+            Ok(res) => DiagnosticCheck::new(
+                &name,
+                CheckStatus::Ok,
+                format!(
+                    "Reachable, version {}",
+                    res.version.as_deref().unwrap_or("unknown")
+                ),
+            ),
+            Err(e) => DiagnosticCheck::new(&name, CheckStatus::Fail, e),
+        };
+        checks.push(check);
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_df_available_space() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on
+/dev/disk3s5     482797652 301234567 181563085      63% /System/Volumes/Data
+";
+        assert_eq!(parse_df_available(output), Some(181563085 * 1024));
+        assert_eq!(parse_df_available("Filesystem"), None);
+    }
+
+    #[test]
+    fn detects_loopback_in_no_proxy() {
+        assert!(excludes_loopback("example.com, localhost"));
+        assert!(excludes_loopback("*"));
+        assert!(!excludes_loopback(""));
+        assert!(!excludes_loopback("example.com"));
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::{
+    constants::{ENV_PROFILES_KEY, SETTINGS_STORE},
+    server,
+};
+
+// Set by the app itself when spawning a server; a profile must not override
+// the credentials or state location.
+const RESERVED: [&str; 6] = [
+    "OPENCODE_SERVER_USERNAME",
+    "OPENCODE_SERVER_PASSWORD",
+    "OPENCODE_CLIENT",
+    "OPENCODE_EXPERIMENTAL_ICON_DISCOVERY",
+    "OPENCODE_EXPERIMENTAL_FILEWATCHER",
+    "XDG_STATE_HOME",
+];
+
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct EnvVar {
+    pub key: String,
+    pub value: String,
+}
+
+fn validate(vars: &[EnvVar]) -> Result<(), String> {
+    for (i, var) in vars.iter().enumerate() {
+        let mut chars = var.key.chars();
+        let valid = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(format!("Invalid variable name: {:?}", var.key));
+        }
+
+        if RESERVED.contains(&var.key.as_str()) {
+            return Err(format!(
+                "{} is set by the app and can't be overridden",
+                var.key
+            ));
+        }
+
+        if vars[..i].iter().any(|v| v.key == var.key) {
+            return Err(format!("Duplicate variable: {}", var.key));
+        }
+    }
+
+    Ok(())
+}
+
+fn load_all(app: &AppHandle) -> Result<HashMap<String, Vec<EnvVar>>, String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    Ok(store
+        .get(ENV_PROFILES_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+/// Variables to inject into the server for `directory`, which must already be
+/// canonical.
+pub fn load(app: &AppHandle, directory: &str) -> Vec<EnvVar> {
+    match load_all(app) {
+        Ok(mut profiles) => profiles.remove(directory).unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!("Failed to load environment profile: {e}");
+            Vec::new()
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_env_profile(app: AppHandle, directory: String) -> Result<Vec<EnvVar>, String> {
+    let directory = server::canonical_directory(&directory)?;
+    Ok(load_all(&app)?.remove(&directory).unwrap_or_default())
+}
+
+/// Replaces the project's environment profile. Takes effect the next time the
+/// project's server starts.
+#[tauri::command]
+#[specta::specta]
+pub fn set_env_profile(app: AppHandle, directory: String, vars: Vec<EnvVar>) -> Result<(), String> {
+    validate(&vars)?;
+    let directory = server::canonical_directory(&directory)?;
+
+    let mut profiles = load_all(&app)?;
+    if vars.is_empty() {
+        profiles.remove(&directory);
+    } else {
+        profiles.insert(directory, vars);
+    }
+
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    let value = serde_json::to_value(&profiles)
+        .map_err(|e| format!("Failed to serialize environment profiles: {}", e))?;
+    store.set(ENV_PROFILES_KEY, value);
+
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(key: &str) -> EnvVar {
+        EnvVar {
+            key: key.to_string(),
+            value: "1".to_string(),
+        }
+    }
+
+    #[test]
+    fn accepts_plain_variable_names() {
+        assert!(validate(&[var("RUST_LOG"), var("_private"), var("A1")]).is_ok());
+        assert!(validate(&[]).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_reserved_and_duplicate_names() {
+        assert!(validate(&[var("")]).is_err());
+        assert!(validate(&[var("1ABC")]).is_err());
+        assert!(validate(&[var("A=B")]).is_err());
+        assert!(validate(&[var("OPENCODE_SERVER_PASSWORD")]).is_err());
+        assert!(validate(&[var("A"), var("A")]).is_err());
+    }
+}
@@ -0,0 +1,273 @@
+#[cfg(windows)]
+use windows::Win32::System::Threading::CREATE_NO_WINDOW;
+
+use tauri::AppHandle;
+
+use crate::{projects::ProjectLocation, server, trust};
+
+const MAX_LOG_ENTRIES: u32 = 1000;
+
+/// One changed path from `git status`. `index` and `worktree` are git's
+/// single-letter status codes (`M`, `A`, `D`, `R`, `?`, ...), with a space
+/// meaning unchanged.
+#[derive(serde::Serialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct GitFileStatus {
+    pub path: String,
+    pub original_path: Option<String>,
+    pub index: String,
+    pub worktree: String,
+}
+
+#[derive(serde::Serialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct GitCommit {
+    pub hash: String,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+}
+
+#[derive(serde::Serialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct GitBranch {
+    pub name: String,
+    pub current: bool,
+    pub upstream: Option<String>,
+}
+
+// Read-only queries run straight against the local repository, so they keep
+// working when the server is remote or down. A repository's own config can
+// name programs for git to run, so untrusted workspaces are refused and the
+// config keys that run code during these queries are overridden.
+async fn git(app: &AppHandle, directory: &str, args: &[&str]) -> Result<String, String> {
+    let directory = server::canonical_directory(directory)?;
+    trust::require(
+        app,
+        &ProjectLocation {
+            path: directory.clone(),
+            host: None,
+        },
+    )?;
+
+    let mut cmd = tokio::process::Command::new("git");
+    cmd.arg("-C")
+        .arg(&directory)
+        .args([
+            "-c",
+            "core.fsmonitor=false",
+            "-c",
+            "log.showSignature=false",
+        ])
+        .args(args)
+        // Don't take index.lock for status refreshes racing the agent's commits.
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .env("LC_ALL", "C");
+
+    #[cfg(windows)]
+    cmd.creation_flags(CREATE_NO_WINDOW.0);
+
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn git_status(app: AppHandle, directory: String) -> Result<Vec<GitFileStatus>, String> {
+    let output = git(
+        &app,
+        &directory,
+        &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+    )
+    .await?;
+
+    Ok(parse_status(&output))
+}
+
+/// Unified diff of the working tree (or the index when `staged`), optionally
+/// limited to one path.
+#[tauri::command]
+#[specta::specta]
+pub async fn git_diff(
+    app: AppHandle,
+    directory: String,
+    path: Option<String>,
+    staged: bool,
+) -> Result<String, String> {
+    let mut args = vec!["diff", "--no-color", "--no-ext-diff", "--no-textconv"];
+    if staged {
+        args.push("--cached");
+    }
+    if let Some(path) = &path {
+        args.extend(["--", path.as_str()]);
+    }
+
+    git(&app, &directory, &args).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn git_log(app: AppHandle, directory: String, n: u32) -> Result<Vec<GitCommit>, String> {
+    let limit = n.min(MAX_LOG_ENTRIES).to_string();
+    let output = git(
+        &app,
+        &directory,
+        &["log", "-n", &limit, "--format=%H%x1f%an%x1f%aI%x1f%s%x1e"],
+    )
+    .await?;
+
+    Ok(parse_log(&output))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn git_branch_list(app: AppHandle, directory: String) -> Result<Vec<GitBranch>, String> {
+    let output = git(
+        &app,
+        &directory,
+        &[
+            "branch",
+            "--format=%(HEAD)%1f%(refname:short)%1f%(upstream:short)",
+        ],
+    )
+    .await?;
+
+    Ok(parse_branches(&output))
+}
+
+// `--porcelain=v1 -z`: "XY path\0", with renames and copies followed by an
+// extra "original\0" entry.
+fn parse_status(output: &str) -> Vec<GitFileStatus> {
+    let mut entries = output.split('\0').filter(|v| !v.is_empty());
+    let mut files = Vec::new();
+
+    while let Some(entry) = entries.next() {
+        let (Some(index), Some(worktree), Some(path)) =
+            (entry.get(0..1), entry.get(1..2), entry.get(3..))
+        else {
+            continue;
+        };
+
+        let original_path = matches!(index, "R" | "C")
+            .then(|| entries.next().map(str::to_string))
+            .flatten();
+
+        files.push(GitFileStatus {
+            path: path.to_string(),
+            original_path,
+            index: index.to_string(),
+            worktree: worktree.to_string(),
+        });
+    }
+
+    files
+}
+
+fn parse_log(output: &str) -> Vec<GitCommit> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').split('\x1f');
+            Some(GitCommit {
+                hash: fields.next().filter(|v| !v.is_empty())?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn parse_branches(output: &str) -> Vec<GitBranch> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x1f');
+            let current = fields.next()? == "*";
+            let name = fields.next()?.to_string();
+            let upstream = fields.next().filter(|v| !v.is_empty()).map(str::to_string);
+
+            Some(GitBranch {
+                name,
+                current,
+                upstream,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_status_with_renames_and_untracked() {
+        let output = " M src/lib.rs\0R  new.rs\0old.rs\0?? notes.txt\0";
+
+        assert_eq!(
+            parse_status(output),
+            [
+                GitFileStatus {
+                    path: "src/lib.rs".into(),
+                    original_path: None,
+                    index: " ".into(),
+                    worktree: "M".into(),
+                },
+                GitFileStatus {
+                    path: "new.rs".into(),
+                    original_path: Some("old.rs".into()),
+                    index: "R".into(),
+                    worktree: " ".into(),
+                },
+                GitFileStatus {
+                    path: "notes.txt".into(),
+                    original_path: None,
+                    index: "?".into(),
+                    worktree: "?".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_log_records() {
+        let output = "abc123\x1fAda\x1f2026-01-02T03:04:05+00:00\x1fFix | pipes\x1e\n\
+                      def456\x1fGrace\x1f2026-01-01T00:00:00+00:00\x1fInitial\x1e\n";
+
+        let commits = parse_log(output);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].hash, "abc123");
+        assert_eq!(commits[0].subject, "Fix | pipes");
+        assert_eq!(commits[1].author, "Grace");
+    }
+
+    #[test]
+    fn parses_branches() {
+        let output = "*\x1fmain\x1forigin/main\n \x1ffeature\x1f\n";
+
+        assert_eq!(
+            parse_branches(output),
+            [
+                GitBranch {
+                    name: "main".into(),
+                    current: true,
+                    upstream: Some("origin/main".into()),
+                },
+                GitBranch {
+                    name: "feature".into(),
+                    current: false,
+                    upstream: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::{
+    approvals,
+    notifications::{self, AppNotification, NotificationKind},
+    server,
+};
+
+const INTERVAL: Duration = Duration::from_secs(10);
+const SLOW_AFTER: Duration = Duration::from_secs(2);
+const DOWN_AFTER_FAILURES: u32 = 3;
+
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Down,
+}
+
+/// Latest probe result for one server, emitted after every check.
+#[derive(tauri_specta::Event, serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+pub struct ServerHealth {
+    pub url: String,
+    pub directory: Option<String>,
+    pub status: HealthStatus,
+    pub latency_ms: Option<u32>,
+    pub consecutive_failures: u32,
+    pub version: Option<String>,
+    pub version_mismatch: bool,
+}
+
+struct Target {
+    url: String,
+    password: Option<String>,
+    directory: Option<String>,
+    last: Option<ServerHealth>,
+}
+
+/// Periodically probes every server the app is connected to.
+#[derive(Default)]
+pub struct HealthMonitor {
+    targets: Mutex<Vec<Target>>,
+}
+
+impl HealthMonitor {
+    pub fn watch(&self, url: String, password: Option<String>, directory: Option<String>) {
+        let mut targets = self.targets.lock().unwrap();
+        targets.retain(|v| v.url != url);
+        targets.push(Target {
+            url,
+            password,
+            directory,
+            last: None,
+        });
+    }
+
+    pub fn unwatch(&self, url: &str) {
+        self.targets.lock().unwrap().retain(|v| v.url != url);
+    }
+
+    /// URLs and credentials of every watched server.
+    pub fn servers(&self) -> Vec<(String, Option<String>)> {
+        self.targets
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|v| (v.url.clone(), v.password.clone()))
+            .collect()
+    }
+
+    fn snapshot(&self) -> Vec<(String, Option<String>, Option<String>, u32)> {
+        self.targets
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|v| {
+                let failures = v.last.as_ref().map_or(0, |v| v.consecutive_failures);
+                (
+                    v.url.clone(),
+                    v.password.clone(),
+                    v.directory.clone(),
+                    failures,
+                )
+            })
+            .collect()
+    }
+
+    fn record(&self, health: ServerHealth) {
+        // The target may have been unwatched while its probe was in flight.
+        if let Some(target) = self
+            .targets
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|v| v.url == health.url)
+        {
+            target.last = Some(health);
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(HealthMonitor::default());
+
+    let app = app.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(INTERVAL).await;
+            check_all(&app).await;
+        }
+    });
+}
+
+/// Probes every server now instead of waiting for the next interval.
+pub fn check_now(app: &AppHandle) {
+    let app = app.clone();
+    tokio::spawn(async move { check_all(&app).await });
+}
+
+async fn check_all(app: &AppHandle) {
+    let monitor = app.state::<HealthMonitor>();
+    let app_version = app.package_info().version.clone();
+
+    for (url, password, directory, failures) in monitor.snapshot() {
+        let started = Instant::now();
+        let res = server::fetch_health(&url, password.as_deref()).await;
+        let latency = started.elapsed();
+
+        let health = match res {
+            Ok(res) => {
+                let version_mismatch = res
+                    .version
+                    .as_deref()
+                    .is_some_and(|v| version_mismatch(&app_version, v));
+
+                ServerHealth {
+                    status: classify(0, Some(latency), version_mismatch),
+                    latency_ms: Some(latency.as_millis().min(u32::MAX as u128) as u32),
+                    consecutive_failures: 0,
+                    version: res.version,
+                    version_mismatch,
+                    url,
+                    directory,
+                }
+            }
+            Err(e) => {
+                tracing::debug!(%url, "Health check failed: {e}");
+
+                if failures + 1 == DOWN_AFTER_FAILURES {
+                    let _ = notifications::notify(
+                        app,
+                        AppNotification {
+                            kind: NotificationKind::ServerDisconnected,
+                            title: "Server unreachable".to_string(),
+                            body: Some(format!("Lost connection to {url}")),
+                        },
+                    );
+                }
+
+                ServerHealth {
+                    status: classify(failures + 1, None, false),
+                    latency_ms: None,
+                    consecutive_failures: failures + 1,
+                    version: None,
+                    version_mismatch: false,
+                    url,
+                    directory,
+                }
+            }
+        };
+
+        let _ = health.emit(app);
+        monitor.record(health);
+    }
+
+    approvals::sync(app);
+}
+
+fn classify(failures: u32, latency: Option<Duration>, version_mismatch: bool) -> HealthStatus {
+    if failures >= DOWN_AFTER_FAILURES {
+        HealthStatus::Down
+    } else if failures > 0 || version_mismatch || latency.is_some_and(|v| v >= SLOW_AFTER) {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Healthy
+    }
+}
+
+// Build metadata and unparseable versions (e.g. local dev builds) never count
+// as a mismatch.
+fn version_mismatch(app: &semver::Version, server: &str) -> bool {
+    semver::Version::parse(server.trim_start_matches('v')).is_ok_and(|v| {
+        (v.major, v.minor, v.patch, &v.pre) != (app.major, app.minor, app.patch, &app.pre)
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_server_health(app: AppHandle) -> Vec<ServerHealth> {
+    app.state::<HealthMonitor>()
+        .targets
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|v| v.last.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_failures_and_latency() {
+        let fast = Some(Duration::from_millis(50));
+        assert_eq!(classify(0, fast, false), HealthStatus::Healthy);
+        assert_eq!(classify(0, Some(SLOW_AFTER), false), HealthStatus::Degraded);
+        assert_eq!(classify(0, fast, true), HealthStatus::Degraded);
+        assert_eq!(classify(1, None, false), HealthStatus::Degraded);
+        assert_eq!(
+            classify(DOWN_AFTER_FAILURES, None, false),
+            HealthStatus::Down
+        );
+    }
+
+    #[test]
+    fn compares_versions_ignoring_build_metadata() {
+        let app = semver::Version::parse("1.2.3").unwrap();
+        assert!(!version_mismatch(&app, "1.2.3"));
+        assert!(!version_mismatch(&app, "v1.2.3+abc"));
+        assert!(!version_mismatch(&app, "local"));
+        assert!(version_mismatch(&app, "1.2.4"));
+        assert!(version_mismatch(&app, "1.2.3-beta.1"));
+    }
+}
@@ -0,0 +1,279 @@
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+use tauri::AppHandle;
+#[cfg(windows)]
+use windows::{
+    Win32::{
+        Foundation::ERROR_NOT_FOUND,
+        Security::Credentials::{
+            CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC, CREDENTIALW, CredDeleteW, CredFree,
+            CredReadW, CredWriteW,
+        },
+    },
+    core::{HSTRING, PWSTR},
+};
+
+// Secrets are stored under the app identifier, one entry per `account`.
+fn service(app: &AppHandle) -> String {
+    app.config().identifier.clone()
+}
+
+/// Reads a secret from the OS credential store: the login keychain on macOS,
+/// the Secret Service on Linux and Credential Manager on Windows.
+pub fn get(app: &AppHandle, account: &str) -> Result<Option<String>, String> {
+    read(&service(app), account)
+}
+
+/// Stores a secret, replacing any previous one for `account`.
+pub fn set(app: &AppHandle, account: &str, secret: &str) -> Result<(), String> {
+    write(&service(app), account, secret)
+}
+
+/// Removes the secret for `account`. Succeeds if there was none.
+pub fn delete(app: &AppHandle, account: &str) -> Result<(), String> {
+    remove(&service(app), account)
+}
+
+// errSecItemNotFound, as returned by `security`.
+#[cfg(target_os = "macos")]
+const NOT_FOUND: i32 = 44;
+
+#[cfg(target_os = "macos")]
+fn security(args: &[&str]) -> Result<std::process::Output, String> {
+    Command::new("security")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run security: {}", e))
+}
+
+#[cfg(target_os = "macos")]
+fn stderr(output: &std::process::Output) -> String {
+    String::from_utf8_lossy(&output.stderr).trim().to_string()
+}
+
+#[cfg(target_os = "macos")]
+fn read(service: &str, account: &str) -> Result<Option<String>, String> {
+    let output = security(&["find-generic-password", "-s", service, "-a", account, "-w"])?;
+    match output.status.code() {
+        Some(0) => Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .trim_end_matches('\n')
+                .to_string(),
+        )),
+        Some(NOT_FOUND) => Ok(None),
+        _ => Err(format!("Failed to read keychain item: {}", stderr(&output))),
+    }
+}
+
+// The secret goes through stdin (hex-encoded, so it needs no quoting)
+// rather than the command line, where other processes could read it.
+#[cfg(target_os = "macos")]
+fn write(service: &str, account: &str, secret: &str) -> Result<(), String> {
+    let hex = secret
+        .bytes()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+
+    let mut child = Command::new("security")
+        .arg("-i")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run security: {}", e))?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to open security stdin".to_string())?
+        .write_all(
+            format!("add-generic-password -U -s {service} -a {account} -X {hex}\n").as_bytes(),
+        )
+        .map_err(|e| format!("Failed to write to security: {}", e))?;
+
+    // Interactive mode exits 0 even when a command fails.
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run security: {}", e))?;
+    match stderr(&output) {
+        e if e.is_empty() && output.status.success() => Ok(()),
+        e => Err(format!("Failed to save keychain item: {e}")),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn remove(service: &str, account: &str) -> Result<(), String> {
+    let output = security(&["delete-generic-password", "-s", service, "-a", account])?;
+    match output.status.code() {
+        Some(0) | Some(NOT_FOUND) => Ok(()),
+        _ => Err(format!(
+            "Failed to delete keychain item: {}",
+            stderr(&output)
+        )),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn secret_tool(args: &[&str]) -> Command {
+    let mut command = Command::new("secret-tool");
+    command.args(args);
+    command
+}
+
+#[cfg(target_os = "linux")]
+fn run(mut command: Command) -> Result<std::process::Output, String> {
+    command.output().map_err(|e| {
+        format!(
+            "Failed to run secret-tool (is libsecret-tools installed?): {}",
+            e
+        )
+    })
+}
+
+// `lookup` exits 1 with no output when nothing matches.
+#[cfg(target_os = "linux")]
+fn read(service: &str, account: &str) -> Result<Option<String>, String> {
+    let output = run(secret_tool(&[
+        "lookup", "service", service, "account", account,
+    ]))?;
+    if output.status.success() {
+        return Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()));
+    }
+
+    match String::from_utf8_lossy(&output.stderr).trim() {
+        "" => Ok(None),
+        e => Err(format!("Failed to read secret: {e}")),
+    }
+}
+
+// `store` reads the secret from stdin when it isn't a terminal.
+#[cfg(target_os = "linux")]
+fn write(service: &str, account: &str, secret: &str) -> Result<(), String> {
+    let mut child = secret_tool(&[
+        "store",
+        &format!("--label={service} ({account})"),
+        "service",
+        service,
+        "account",
+        account,
+    ])
+    .stdin(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|e| {
+        format!(
+            "Failed to run secret-tool (is libsecret-tools installed?): {}",
+            e
+        )
+    })?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to open secret-tool stdin".to_string())?
+        .write_all(secret.as_bytes())
+        .map_err(|e| format!("Failed to write to secret-tool: {}", e))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run secret-tool: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to save secret: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn remove(service: &str, account: &str) -> Result<(), String> {
+    run(secret_tool(&[
+        "clear", "service", service, "account", account,
+    ]))
+    .map(|_| ())
+}
+
+#[cfg(windows)]
+fn target(service: &str, account: &str) -> HSTRING {
+    HSTRING::from(format!("{service}/{account}"))
+}
+
+#[cfg(windows)]
+fn not_found(e: &windows::core::Error) -> bool {
+    e.code() == ERROR_NOT_FOUND.to_hresult()
+}
+
+#[cfg(windows)]
+fn read(service: &str, account: &str) -> Result<Option<String>, String> {
+    let mut credential = std::ptr::null_mut::<CREDENTIALW>();
+    match unsafe {
+        CredReadW(
+            &target(service, account),
+            CRED_TYPE_GENERIC,
+            None,
+            &mut credential,
+        )
+    } {
+        Ok(()) => {}
+        Err(e) if not_found(&e) => return Ok(None),
+        Err(e) => return Err(format!("Failed to read credential: {}", e)),
+    }
+
+    let secret = unsafe {
+        let blob = std::slice::from_raw_parts(
+            (*credential).CredentialBlob,
+            (*credential).CredentialBlobSize as usize,
+        );
+        let secret = String::from_utf8_lossy(blob).to_string();
+        CredFree(credential.cast());
+        secret
+    };
+    Ok(Some(secret))
+}
+
+#[cfg(windows)]
+fn write(service: &str, account: &str, secret: &str) -> Result<(), String> {
+    let target = target(service, account);
+    let user = HSTRING::from(account);
+    let mut blob = secret.as_bytes().to_vec();
+
+    let credential = CREDENTIALW {
+        Type: CRED_TYPE_GENERIC,
+        TargetName: PWSTR(target.as_ptr().cast_mut()),
+        UserName: PWSTR(user.as_ptr().cast_mut()),
+        CredentialBlobSize: blob.len() as u32,
+        CredentialBlob: blob.as_mut_ptr(),
+        Persist: CRED_PERSIST_LOCAL_MACHINE,
+        ..Default::default()
+    };
+
+    unsafe { CredWriteW(&credential, 0) }.map_err(|e| format!("Failed to save credential: {}", e))
+}
+
+#[cfg(windows)]
+fn remove(service: &str, account: &str) -> Result<(), String> {
+    match unsafe { CredDeleteW(&target(service, account), CRED_TYPE_GENERIC, None) } {
+        Err(e) if !not_found(&e) => Err(format!("Failed to delete credential: {}", e)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+const UNSUPPORTED: &str = "No credential store on this platform";
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn read(_service: &str, _account: &str) -> Result<Option<String>, String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn write(_service: &str, _account: &str, _secret: &str) -> Result<(), String> {
+    Err(UNSUPPORTED.to_string())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+fn remove(_service: &str, _account: &str) -> Result<(), String> {
+    Err(UNSUPPORTED.to_string())
+}
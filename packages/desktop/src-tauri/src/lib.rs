@@ -1,12 +1,35 @@
+mod approvals;
+mod autostart;
+mod certs;
 mod cli;
 mod constants;
+mod crash;
+mod deep_link;
+mod diagnostics;
+mod env_profiles;
+mod git;
+mod health;
+mod keychain;
 #[cfg(target_os = "linux")]
 pub mod linux_display;
 #[cfg(target_os = "linux")]
 pub mod linux_windowing;
 mod logging;
 mod markdown;
+mod migrations;
+mod network;
+mod notifications;
+mod power;
+mod privacy;
+mod projects;
+mod proxy;
 mod server;
+mod server_logs;
+mod settings;
+mod shell_integration;
+mod state_file;
+mod trust;
+mod updater;
 mod window_customizer;
 mod windows;
 
@@ -35,6 +58,7 @@ use tokio::{
 use crate::cli::{sqlite_migration::SqliteMigrationProgress, sync_cli};
 use crate::constants::*;
 use crate::server::get_saved_server_url;
+use crate::state_file::{Connection, StateFile};
 use crate::windows::{LoadingWindow, MainWindow};
 
 #[derive(Clone, serde::Serialize, specta::Type, Debug)]
@@ -393,11 +417,17 @@ fn check_linux_app(app_name: &str) -> bool {
 #[tauri::command]
 #[specta::specta]
 fn wsl_path(path: String, mode: Option<WslPathMode>) -> Result<String, String> {
+    convert_wsl_path(&path, mode.unwrap_or(WslPathMode::Linux))
+}
+
+/// Converts `path` between its Windows and WSL forms with `wslpath`.
+/// Returns it unchanged off Windows.
+fn convert_wsl_path(path: &str, mode: WslPathMode) -> Result<String, String> {
     if !cfg!(windows) {
-        return Ok(path);
+        return Ok(path.to_string());
     }
 
-    let flag = match mode.unwrap_or(WslPathMode::Linux) {
+    let flag = match mode {
         WslPathMode::Windows => "-w",
         WslPathMode::Linux => "-u",
     };
@@ -412,7 +442,7 @@ fn wsl_path(path: String, mode: Option<WslPathMode>) -> Result<String, String> {
             .map_err(|e| format!("Failed to run wslpath: {e}"))?
     } else {
         Command::new("wsl")
-            .args(["-e", "wslpath", flag, &path])
+            .args(["-e", "wslpath", flag, path])
             .output()
             .map_err(|e| format!("Failed to run wslpath: {e}"))?
     };
@@ -441,11 +471,17 @@ pub fn run() {
         .output();
 
     let mut builder = tauri::Builder::default()
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            // `OpenCode open <dir>` from a shell opens the directory here
+            deep_link::open_args(app, &args, &cwd);
+
             // Focus existing window when another instance is launched
             if let Some(window) = app.get_webview_window(MainWindow::LABEL) {
                 let _ = window.set_focus();
                 let _ = window.unminimize();
+            } else if autostart::background_mode(app) {
+                // Running in the background with every window closed.
+                let _ = MainWindow::create(app);
             }
         }))
         .plugin(tauri_plugin_deep_link::init())
@@ -474,9 +510,15 @@ pub fn run() {
                 .path()
                 .app_log_dir()
                 .expect("failed to resolve app log dir");
+            // Enable identifier scrubbing before anything is logged.
+            privacy::init(&handle);
+
             // Hold the guard in managed state so it lives for the app's lifetime,
             // ensuring all buffered logs are flushed on shutdown.
             handle.manage(logging::init(&log_dir));
+            crash::install(&log_dir);
+
+            migrations::run(&handle);
 
             builder.mount_events(&handle);
             tauri::async_runtime::spawn(initialize(handle));
@@ -492,10 +534,37 @@ pub fn run() {
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
         .run(|app, event| {
+            // `code` is only set for explicit exits, not the last window closing.
+            if let RunEvent::ExitRequested {
+                api, code: None, ..
+            } = &event
+                && autostart::background_mode(app)
+            {
+                tracing::info!("Last window closed, staying in background");
+                api.prevent_exit();
+            }
+
+            #[cfg(target_os = "macos")]
+            if let RunEvent::Reopen {
+                has_visible_windows: false,
+                ..
+            } = &event
+            {
+                let _ = MainWindow::create(app);
+            }
+
             if let RunEvent::Exit = event {
                 tracing::info!("Received Exit");
 
                 kill_sidecar(app.clone());
+
+                if let Some(servers) = app.try_state::<server::ProjectServers>() {
+                    servers.stop_all();
+                }
+
+                if let Some(state) = app.try_state::<StateFile>() {
+                    state.clear();
+                }
             }
         });
 }
@@ -511,16 +580,88 @@ fn make_specta_builder() -> tauri_specta::Builder<tauri::Wry> {
             server::set_default_server_url,
             server::get_wsl_config,
             server::set_wsl_config,
+            server::get_local_server_config,
+            server::set_local_server_config,
+            server::start_project_server,
+            server::stop_project_server,
+            server::list_project_servers,
+            health::get_server_health,
+            server_logs::server_logs,
+            server_logs::server_logs_follow,
+            server_logs::server_logs_unfollow,
+            diagnostics::run_diagnostics,
+            windows::window_open_project,
+            windows::window_list,
+            windows::window_focus,
+            windows::set_badge_count,
+            windows::set_taskbar_progress,
+            autostart::get_autostart,
+            autostart::set_autostart,
+            autostart::get_background_mode,
+            autostart::set_background_mode,
+            power::power_inhibit,
+            power::power_release,
+            power::get_prevent_sleep,
+            power::set_prevent_sleep,
+            projects::pick_project,
+            projects::list_recent_projects,
+            projects::add_recent_project,
+            projects::pin_recent_project,
+            projects::remove_recent_project,
+            shell_integration::get_shell_integration,
+            shell_integration::install_shell_integration,
+            shell_integration::uninstall_shell_integration,
+            git::git_status,
+            git::git_diff,
+            git::git_log,
+            git::git_branch_list,
+            trust::trust_get,
+            trust::trust_set,
+            trust::trust_list,
+            env_profiles::get_env_profile,
+            env_profiles::set_env_profile,
+            settings::settings_get,
+            settings::settings_set,
             get_display_backend,
             set_display_backend,
             markdown::parse_markdown_command,
             check_app_exists,
             wsl_path,
-            resolve_app_path
+            resolve_app_path,
+            privacy::get_privacy_mode,
+            privacy::set_privacy_mode,
+            proxy::proxy_reload,
+            proxy::get_proxy_config,
+            proxy::set_proxy_config,
+            certs::get_extra_ca_certs,
+            certs::set_extra_ca_certs,
+            logging::log_set_level,
+            logging::log_open_folder,
+            updater::get_update_channel,
+            updater::list_update_channels,
+            updater::set_update_channel,
+            updater::update_defer,
+            updater::update_check,
+            updater::update_install,
+            deep_link::confirm_deep_link,
+            deep_link::create_auth_state,
+            deep_link::drain_pending_deep_links,
+            deep_link::create_deep_link,
+            deep_link::resolve_share_link,
+            deep_link::policy::get_deep_link_policy,
+            deep_link::policy::set_deep_link_policy
         ])
         .events(tauri_specta::collect_events![
             LoadingWindowComplete,
-            SqliteMigrationProgress
+            SqliteMigrationProgress,
+            deep_link::DeepLinkBatch,
+            deep_link::DeepLinkConfirmation,
+            server::ServerCrashed,
+            server::ServerRestarted,
+            health::ServerHealth,
+            network::NetworkChanged,
+            settings::SettingsChanged,
+            updater::UpdateProgress
         ])
         .error_handling(tauri_specta::ErrorHandlingMode::Throw)
 }
@@ -547,6 +688,9 @@ struct LoadingWindowComplete;
 async fn initialize(app: AppHandle) {
     tracing::info!("Initializing app");
 
+    proxy::init(&app);
+    certs::init(&app);
+
     let (init_tx, init_rx) = watch::channel(InitStep::ServerWaiting);
 
     setup_app(&app, init_rx);
@@ -614,25 +758,30 @@ async fn initialize(app: AppHandle) {
                     Some(
                         async move {
                             let res = timeout(Duration::from_secs(30), health_check.0).await;
-                            let err = match res {
-                                Ok(Ok(Ok(()))) => None,
-                                Ok(Ok(Err(e))) => Some(e),
-                                Ok(Err(e)) => Some(format!("Health check task failed: {e}")),
-                                Err(_) => Some("Health check timed out".to_string()),
+                            let res = match res {
+                                Ok(Ok(Ok(supervisor))) => Ok(supervisor),
+                                Ok(Ok(Err(e))) => Err(e),
+                                Ok(Err(e)) => Err(format!("Health check task failed: {e}")),
+                                Err(_) => Err("Health check timed out".to_string()),
                             };
 
-                            if let Some(err) = err {
-                                let _ = child.kill();
+                            let supervisor = match res {
+                                Ok(supervisor) => supervisor,
+                                Err(err) => {
+                                    let _ = child.kill();
 
-                                return Err(format!(
-                                    "Failed to spawn OpenCode Server ({err}). Logs:\n{}",
-                                    get_logs()
-                                ));
-                            }
+                                    return Err(format!(
+                                        "Failed to spawn OpenCode Server ({err}). Logs:\n{}",
+                                        get_logs()
+                                    ));
+                                }
+                            };
 
                             tracing::info!("CLI health check OK");
 
-                            app.state::<ServerState>().set_child(Some(child));
+                            let server_state = app.state::<ServerState>();
+                            server_state.set_child(Some(child));
+                            tokio::spawn(supervisor.run(server_state.child.clone()));
 
                             Ok(ServerReadyData { url, username,password, is_sidecar: true })
                         }
@@ -669,7 +818,12 @@ async fn initialize(app: AppHandle) {
     .map_err(|_| ())
     .shared();
 
-    let loading_window = if needs_sqlite_migration
+    let start_hidden = autostart::start_hidden(&app);
+
+    let loading_window = if start_hidden {
+        tracing::info!("Launched at login in background mode, not showing a window");
+        None
+    } else if needs_sqlite_migration
         && timeout(Duration::from_secs(1), loading_task.clone())
             .await
             .is_err()
@@ -687,6 +841,20 @@ async fn initialize(app: AppHandle) {
 
     let _ = loading_task.await;
 
+    if let Ok(Ok(data)) = app.state::<ServerState>().status.clone().await {
+        app.state::<health::HealthMonitor>()
+            .watch(data.url.clone(), data.password, None);
+
+        if let Some(state) = app.try_state::<StateFile>() {
+            state.add(Connection {
+                url: data.url,
+                sidecar: data.is_sidecar,
+                destination: None,
+                directory: None,
+            });
+        }
+    }
+
     tracing::info!("Loading done, completing initialisation");
     let _ = init_tx.send(InitStep::Done);
 
@@ -696,7 +864,9 @@ async fn initialize(app: AppHandle) {
         tracing::info!("Loading window completed");
     }
 
-    MainWindow::create(&app).expect("Failed to create main window");
+    if !start_hidden {
+        MainWindow::create(&app).expect("Failed to create main window");
+    }
 
     if let Some(loading_window) = loading_window {
         let _ = loading_window.close();
@@ -707,7 +877,22 @@ fn setup_app(app: &tauri::AppHandle, init_rx: watch::Receiver<InitStep>) {
     #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
     app.deep_link().register_all().ok();
 
+    deep_link::setup(app);
+    crash::init(app);
+    power::init(app);
+    settings::init(app);
+    app.manage(windows::ProjectWindows::default());
+    app.manage(updater::PendingUpdate::default());
+
     app.manage(InitState { current: init_rx });
+    app.manage(server::ProjectServers::default());
+    server_logs::ServerLogs::init(app);
+
+    approvals::init(app);
+    health::init(app);
+    network::init(app);
+
+    StateFile::init(app);
 }
 
 fn spawn_cli_sync_task(app: AppHandle) {
@@ -747,9 +932,14 @@ async fn setup_server_connection(app: AppHandle) -> ServerConnection {
         // Remote default server: fall through and also spawn a local sidecar
     }
 
-    let local_port = get_sidecar_port();
-    let hostname = "127.0.0.1";
-    let local_url = format!("http://{hostname}:{local_port}");
+    let config = server::local_server_config(&app).unwrap_or_default();
+    let local_port = config.port.unwrap_or_else(get_sidecar_port);
+    let hostname = config.bind_hostname();
+    let local_url = server::local_url(&hostname, local_port);
+
+    if config.lan_access {
+        tracing::warn!("LAN access enabled, local server will listen on all interfaces");
+    }
 
     tracing::debug!(url = %local_url, "Checking health of local server");
     if server::check_health(&local_url, None).await {
@@ -757,11 +947,20 @@ async fn setup_server_connection(app: AppHandle) -> ServerConnection {
         return ServerConnection::Existing { url: local_url };
     }
 
+    let local_port = server::resolve_port_conflict(&app, local_port).await;
+    let local_url = server::local_url(&hostname, local_port);
+
     let password = uuid::Uuid::new_v4().to_string();
 
     tracing::info!("Spawning new local server");
-    let (child, health_check) =
-        server::spawn_local_server(app, hostname.to_string(), local_port, password.clone());
+    let (child, health_check) = server::spawn_local_server(
+        app,
+        hostname,
+        local_port,
+        password.clone(),
+        None,
+    )
+    .expect("Failed to spawn opencode");
 
     ServerConnection::CLI {
         url: local_url,
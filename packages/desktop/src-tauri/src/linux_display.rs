@@ -11,6 +11,7 @@ pub const LINUX_DISPLAY_CONFIG_KEY: &str = "linuxDisplayConfig";
 #[derive(Default, Serialize, Deserialize)]
 struct DisplayConfig {
     wayland: Option<bool>,
+    decorations: Option<String>,
 }
 
 fn dir() -> Option<PathBuf> {
@@ -25,29 +26,50 @@ fn path() -> Option<PathBuf> {
     dir().map(|dir| dir.join(SETTINGS_STORE))
 }
 
-pub fn read_wayland() -> Option<bool> {
+// Read straight from disk: these are needed before the app (and so the store
+// plugin) has started.
+fn read_config() -> Option<DisplayConfig> {
     let raw = std::fs::read_to_string(path()?).ok()?;
     let root = serde_json::from_str::<serde_json::Value>(&raw)
         .ok()?
         .get(LINUX_DISPLAY_CONFIG_KEY)
         .cloned()?;
-    serde_json::from_value::<DisplayConfig>(root).ok()?.wayland
+    serde_json::from_value::<DisplayConfig>(root).ok()
 }
 
-pub fn write_wayland(app: &AppHandle, value: bool) -> Result<(), String> {
+pub fn read_wayland() -> Option<bool> {
+    read_config()?.wayland
+}
+
+/// Same values as `OC_LINUX_DECORATIONS` (`auto`, `native`, `none`), which
+/// takes precedence when set.
+pub fn read_decorations() -> Option<String> {
+    read_config()?.decorations
+}
+
+fn write(app: &AppHandle, update: impl FnOnce(&mut DisplayConfig)) -> Result<(), String> {
     let store = app
         .store(SETTINGS_STORE)
         .map_err(|e| format!("Failed to open settings store: {}", e))?;
 
-    store.set(
-        LINUX_DISPLAY_CONFIG_KEY,
-        json!(DisplayConfig {
-            wayland: Some(value),
-        }),
-    );
+    let mut config = store
+        .get(LINUX_DISPLAY_CONFIG_KEY)
+        .and_then(|v| serde_json::from_value::<DisplayConfig>(v).ok())
+        .unwrap_or_default();
+    update(&mut config);
+
+    store.set(LINUX_DISPLAY_CONFIG_KEY, json!(config));
     store
         .save()
         .map_err(|e| format!("Failed to save settings store: {}", e))?;
 
     Ok(())
 }
+
+pub fn write_wayland(app: &AppHandle, value: bool) -> Result<(), String> {
+    write(app, |config| config.wayland = Some(value))
+}
+
+pub fn write_decorations(app: &AppHandle, value: Option<String>) -> Result<(), String> {
+    write(app, |config| config.decorations = value)
+}
@@ -1,49 +1,103 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
-
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{
+    EnvFilter, Registry, fmt, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
+
+use crate::privacy::Scrubbed;
+
+const LOG_PREFIX: &str = "opencode-desktop";
 const MAX_LOG_AGE_DAYS: u64 = 7;
 const TAIL_LINES: usize = 1000;
 
-static LOG_PATH: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+static LOG_DIR: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+static FILTER: std::sync::OnceLock<reload::Handle<EnvFilter, Registry>> =
+    std::sync::OnceLock::new();
+
+fn default_filter() -> EnvFilter {
+    if cfg!(debug_assertions) {
+        filter_for_level("debug")
+    } else {
+        filter_for_level("info")
+    }
+}
+
+fn filter_for_level(level: &str) -> EnvFilter {
+    EnvFilter::new(format!(
+        "opencode_lib={level},opencode_desktop={level},sidecar={level}"
+    ))
+}
+
+// A bare level applies to the app's own targets; anything else is taken as
+// full `RUST_LOG` directives, e.g. "info,opencode_lib::server=trace".
+fn parse_filter(filter: &str) -> Result<EnvFilter, String> {
+    let filter = filter.trim();
+    match filter.to_ascii_lowercase().as_str() {
+        "" | "default" => Ok(default_filter()),
+        level @ ("trace" | "debug" | "info" | "warn" | "error" | "off") => {
+            Ok(filter_for_level(level))
+        }
+        _ => EnvFilter::try_new(filter).map_err(|e| format!("Invalid log filter: {}", e)),
+    }
+}
 
 pub fn init(log_dir: &Path) -> WorkerGuard {
     std::fs::create_dir_all(log_dir).expect("failed to create log directory");
 
     cleanup(log_dir);
 
-    let timestamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
-    let filename = format!("opencode-desktop_{timestamp}.log");
-    let log_path = log_dir.join(&filename);
-
-    LOG_PATH
-        .set(log_path.clone())
+    LOG_DIR
+        .set(log_dir.to_path_buf())
         .expect("logging already initialized");
 
-    let file = File::create(&log_path).expect("failed to create log file");
+    // One file per day (opencode-desktop.2026-01-31.log), so a long-running
+    // app doesn't grow a single file forever.
+    let file = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_AGE_DAYS as usize)
+        .build(log_dir)
+        .expect("failed to create log file");
     let (non_blocking, guard) = tracing_appender::non_blocking(file);
 
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-        if cfg!(debug_assertions) {
-            EnvFilter::new("opencode_lib=debug,opencode_desktop=debug,sidecar=debug")
-        } else {
-            EnvFilter::new("opencode_lib=info,opencode_desktop=info,sidecar=info")
-        }
-    });
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| default_filter());
+    let (filter, handle) = reload::Layer::new(filter);
+    let _ = FILTER.set(handle);
 
     tracing_subscriber::registry()
         .with(filter)
-        .with(fmt::layer().with_writer(std::io::stderr))
-        .with(fmt::layer().with_writer(non_blocking).with_ansi(false))
+        .with(fmt::layer().with_writer(Scrubbed(std::io::stderr)))
+        .with(
+            fmt::layer()
+                .with_writer(Scrubbed(non_blocking))
+                .with_ansi(false),
+        )
         .init();
 
     guard
 }
 
+// The file currently being written: the most recently modified log.
+fn current_log(log_dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(log_dir)
+        .ok()?
+        .flatten()
+        .filter(|v| v.file_name().to_string_lossy().starts_with(LOG_PREFIX))
+        .filter_map(|v| Some((v.metadata().ok()?.modified().ok()?, v.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
 pub fn tail() -> String {
-    let Some(path) = LOG_PATH.get() else {
+    let Some(path) = LOG_DIR.get().and_then(|v| current_log(v)) else {
         return String::new();
     };
 
@@ -57,6 +111,36 @@ pub fn tail() -> String {
     lines[start..].join("\n")
 }
 
+/// Changes what gets logged until the app restarts. Takes a level ("debug"),
+/// "default", or `RUST_LOG`-style directives.
+#[tauri::command]
+#[specta::specta]
+pub fn log_set_level(filter: String) -> Result<(), String> {
+    let new = parse_filter(&filter)?;
+    let handle = FILTER
+        .get()
+        .ok_or_else(|| "Logging is not initialized".to_string())?;
+    handle
+        .reload(new)
+        .map_err(|e| format!("Failed to set log level: {}", e))?;
+
+    tracing::info!(filter = %filter, "Log filter changed");
+    Ok(())
+}
+
+/// Opens the log directory in the system file manager.
+#[tauri::command]
+#[specta::specta]
+pub fn log_open_folder(app: AppHandle) -> Result<(), String> {
+    let dir = LOG_DIR
+        .get()
+        .ok_or_else(|| "Logging is not initialized".to_string())?;
+
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open log folder: {}", e))
+}
+
 fn cleanup(log_dir: &Path) {
     let cutoff = std::time::SystemTime::now()
         - std::time::Duration::from_secs(MAX_LOG_AGE_DAYS * 24 * 60 * 60);
@@ -74,3 +158,19 @@ fn cleanup(log_dir: &Path) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_levels_and_directives() {
+        assert_eq!(
+            parse_filter(" DEBUG ").unwrap().to_string(),
+            filter_for_level("debug").to_string()
+        );
+        assert!(parse_filter("").is_ok());
+        assert!(parse_filter("info,opencode_lib::server=trace").is_ok());
+        assert!(parse_filter("opencode_lib=loud").is_err());
+    }
+}
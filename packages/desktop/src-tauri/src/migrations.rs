@@ -0,0 +1,183 @@
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::constants::{DEFAULT_SERVER_URL_KEY, SCHEMA_VERSION_KEY, SETTINGS_STORE};
+
+type Settings = Map<String, Value>;
+
+/// Ordered settings migrations. Entry `i` upgrades the store from schema
+/// version `i` to `i + 1`, so new migrations must only ever be appended.
+const MIGRATIONS: &[fn(&mut Settings)] = &[drop_empty_server_url];
+
+pub const CURRENT_VERSION: u64 = MIGRATIONS.len() as u64;
+
+/// Brings the settings store up to `CURRENT_VERSION` before anything reads it.
+/// Must run before the store plugin loads the file, otherwise its in-memory
+/// copy would overwrite the migrated one on the next save.
+pub fn run(app: &AppHandle) {
+    let Ok(dir) = app.path().app_data_dir() else {
+        tracing::warn!("Could not resolve app data dir, skipping settings migration");
+        return;
+    };
+
+    if let Err(e) = migrate_file(&dir.join(SETTINGS_STORE)) {
+        tracing::error!("Failed to migrate settings: {e}");
+    }
+}
+
+fn migrate_file(path: &Path) -> Result<(), String> {
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return Ok(());
+    };
+
+    let mut settings = serde_json::from_str::<Settings>(&raw)
+        .map_err(|e| format!("Failed to parse settings: {}", e))?;
+
+    let from = version(&settings);
+    if from > CURRENT_VERSION {
+        tracing::warn!(
+            from,
+            current = CURRENT_VERSION,
+            "Settings were written by a newer version, leaving them untouched"
+        );
+        return Ok(());
+    }
+    if from == CURRENT_VERSION {
+        return Ok(());
+    }
+
+    let backup = backup_path(path, from);
+    std::fs::copy(path, &backup).map_err(|e| format!("Failed to back up settings: {}", e))?;
+
+    migrate(&mut settings);
+
+    let tmp = path.with_extension("tmp");
+    let json = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    std::fs::write(&tmp, json).map_err(|e| format!("Failed to write settings: {}", e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace settings: {}", e))?;
+
+    tracing::info!(
+        from,
+        to = CURRENT_VERSION,
+        backup = %backup.display(),
+        "Migrated settings"
+    );
+
+    Ok(())
+}
+
+fn migrate(settings: &mut Settings) {
+    let from = version(settings) as usize;
+
+    for migration in MIGRATIONS.iter().skip(from) {
+        migration(settings);
+    }
+
+    settings.insert(SCHEMA_VERSION_KEY.to_string(), Value::from(CURRENT_VERSION));
+}
+
+fn version(settings: &Settings) -> u64 {
+    settings
+        .get(SCHEMA_VERSION_KEY)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0)
+}
+
+fn backup_path(path: &Path, version: u64) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{version}.bak"));
+    path.with_file_name(name)
+}
+
+// 0 -> 1: older builds could persist an empty default server URL, which was then
+// treated as a custom server and failed every startup health check.
+fn drop_empty_server_url(settings: &mut Settings) {
+    let empty = settings
+        .get(DEFAULT_SERVER_URL_KEY)
+        .is_some_and(|v| v.as_str().is_none_or(|v| v.trim().is_empty()));
+
+    if empty {
+        settings.remove(DEFAULT_SERVER_URL_KEY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn settings(value: Value) -> Settings {
+        value.as_object().cloned().unwrap()
+    }
+
+    fn temp_store(contents: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("opencode-migrations-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_STORE);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn drop_empty_server_url_removes_blank_values() {
+        let mut value = settings(json!({ "defaultServerUrl": "  " }));
+        drop_empty_server_url(&mut value);
+        assert!(!value.contains_key(DEFAULT_SERVER_URL_KEY));
+
+        let mut value = settings(json!({ "defaultServerUrl": null }));
+        drop_empty_server_url(&mut value);
+        assert!(!value.contains_key(DEFAULT_SERVER_URL_KEY));
+    }
+
+    #[test]
+    fn drop_empty_server_url_keeps_real_urls() {
+        let mut value = settings(json!({ "defaultServerUrl": "http://localhost:4096" }));
+        drop_empty_server_url(&mut value);
+        assert_eq!(value[DEFAULT_SERVER_URL_KEY], "http://localhost:4096");
+    }
+
+    #[test]
+    fn migrate_stamps_current_version() {
+        let mut value = settings(json!({ "wslEnabled": true }));
+        migrate(&mut value);
+        assert_eq!(version(&value), CURRENT_VERSION);
+        assert_eq!(value["wslEnabled"], true);
+    }
+
+    #[test]
+    fn migrate_file_writes_backup_of_previous_contents() {
+        let original = r#"{"defaultServerUrl":""}"#;
+        let path = temp_store(original);
+
+        migrate_file(&path).unwrap();
+
+        let backup = std::fs::read_to_string(backup_path(&path, 0)).unwrap();
+        assert_eq!(backup, original);
+
+        let migrated =
+            serde_json::from_str::<Settings>(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(version(&migrated), CURRENT_VERSION);
+        assert!(!migrated.contains_key(DEFAULT_SERVER_URL_KEY));
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn migrate_file_leaves_newer_schema_untouched() {
+        let original = format!(
+            r#"{{"schemaVersion":{},"defaultServerUrl":""}}"#,
+            CURRENT_VERSION + 1
+        );
+        let path = temp_store(&original);
+
+        migrate_file(&path).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+        assert!(!backup_path(&path, CURRENT_VERSION + 1).exists());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+use tauri::AppHandle;
+use tauri_specta::Event;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+use crate::health;
+
+// Only used when the OS change notifications can't be set up.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+// A network switch arrives as a burst of link, address and route messages.
+const SETTLE: Duration = Duration::from_millis(500);
+
+/// Emitted when the default route changes: an interface going up or down,
+/// switching Wi-Fi networks or connecting a VPN.
+#[derive(tauri_specta::Event, serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+pub struct NetworkChanged {
+    pub online: bool,
+    pub local_address: Option<String>,
+}
+
+/// Watches for network changes and probes every server straight away instead
+/// of waiting for the next health check interval.
+pub fn init(app: &AppHandle) {
+    let app = app.clone();
+    tokio::spawn(async move {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut notified = match watch(tx) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Polling for network changes: {e}");
+                false
+            }
+        };
+        let mut current = route().await;
+
+        loop {
+            if notified {
+                if rx.recv().await.is_none() {
+                    tracing::warn!("Network change notifications stopped, polling instead");
+                    notified = false;
+                    continue;
+                }
+                tokio::time::sleep(SETTLE).await;
+                while rx.try_recv().is_ok() {}
+            } else {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+
+            let next = route().await;
+            if next == current {
+                continue;
+            }
+
+            tracing::info!(
+                from = ?current,
+                to = ?next,
+                "Network changed, checking servers"
+            );
+            current = next;
+
+            let _ = NetworkChanged {
+                online: next.is_some(),
+                local_address: next.map(|v| v.to_string()),
+            }
+            .emit(&app);
+
+            health::check_now(&app);
+        }
+    });
+}
+
+async fn route() -> Option<IpAddr> {
+    tokio::task::spawn_blocking(|| {
+        local_address(Ipv4Addr::new(192, 0, 2, 1).into())
+            .or_else(|| local_address(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into()))
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+// Connecting a UDP socket sends nothing but makes the OS pick the source
+// address of the default route, which changes with the active network.
+fn local_address(target: IpAddr) -> Option<IpAddr> {
+    let bind: SocketAddr = match target {
+        IpAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        IpAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect((target, 9)).ok()?;
+    socket
+        .local_addr()
+        .ok()
+        .map(|v| v.ip())
+        .filter(|v| !v.is_unspecified())
+}
+
+/// Asks the OS to report address, link and route changes on `tx`.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn watch(tx: UnboundedSender<()>) -> Result<(), String> {
+    use std::io::Read;
+
+    let mut socket = open_route_socket()
+        .map(std::fs::File::from)
+        .map_err(|e| format!("Failed to open routing socket: {}", e))?;
+
+    std::thread::spawn(move || {
+        let mut buf = [0u8; 8192];
+        while let Ok(n @ 1..) = socket.read(&mut buf) {
+            if relevant(&buf[..n]) && tx.send(()).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// rtnetlink, subscribed to link, address and route changes.
+#[cfg(target_os = "linux")]
+fn open_route_socket() -> std::io::Result<std::os::fd::OwnedFd> {
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+            libc::NETLINK_ROUTE,
+        )
+    };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+    addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+    addr.nl_groups = (libc::RTMGRP_LINK
+        | libc::RTMGRP_IPV4_IFADDR
+        | libc::RTMGRP_IPV6_IFADDR
+        | libc::RTMGRP_IPV4_ROUTE
+        | libc::RTMGRP_IPV6_ROUTE) as u32;
+
+    let bound = unsafe {
+        libc::bind(
+            fd.as_raw_fd(),
+            (&raw const addr).cast(),
+            std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        )
+    };
+    if bound < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(fd)
+}
+
+// The PF_ROUTE socket behind `route monitor` and SCNetworkReachability.
+#[cfg(target_os = "macos")]
+fn open_route_socket() -> std::io::Result<std::os::fd::OwnedFd> {
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    let fd = unsafe { libc::socket(libc::PF_ROUTE, libc::SOCK_RAW, libc::AF_UNSPEC) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+// Only membership messages are subscribed to.
+#[cfg(target_os = "linux")]
+fn relevant(_message: &[u8]) -> bool {
+    true
+}
+
+// The routing socket also echoes every lookup (RTM_GET, RTM_MISS); only
+// interface, address and route table edits matter. The type is the fourth
+// byte of every message header.
+#[cfg(target_os = "macos")]
+fn relevant(message: &[u8]) -> bool {
+    message.get(3).is_some_and(|kind| {
+        [
+            libc::RTM_ADD,
+            libc::RTM_DELETE,
+            libc::RTM_CHANGE,
+            libc::RTM_NEWADDR,
+            libc::RTM_DELADDR,
+            libc::RTM_IFINFO,
+        ]
+        .contains(&libc::c_int::from(*kind))
+    })
+}
+
+/// Asks the OS to report interface and address changes on `tx`.
+#[cfg(windows)]
+fn watch(tx: UnboundedSender<()>) -> Result<(), String> {
+    use std::ffi::c_void;
+    use windows::Win32::{
+        Foundation::HANDLE,
+        NetworkManagement::IpHelper::{
+            MIB_IPINTERFACE_ROW, MIB_NOTIFICATION_TYPE, MIB_UNICASTIPADDRESS_ROW,
+            NotifyIpInterfaceChange, NotifyUnicastIpAddressChange,
+        },
+        Networking::WinSock::AF_UNSPEC,
+    };
+
+    fn notify(context: *const c_void) {
+        let tx = unsafe { &*context.cast::<UnboundedSender<()>>() };
+        let _ = tx.send(());
+    }
+    unsafe extern "system" fn interface_changed(
+        context: *const c_void,
+        _row: *const MIB_IPINTERFACE_ROW,
+        _kind: MIB_NOTIFICATION_TYPE,
+    ) {
+        notify(context)
+    }
+    unsafe extern "system" fn address_changed(
+        context: *const c_void,
+        _row: *const MIB_UNICASTIPADDRESS_ROW,
+        _kind: MIB_NOTIFICATION_TYPE,
+    ) {
+        notify(context)
+    }
+
+    // Both registrations last as long as the app, so the sender is leaked.
+    let context = Box::into_raw(Box::new(tx)).cast::<c_void>().cast_const();
+    let mut handle = HANDLE::default();
+    unsafe {
+        NotifyIpInterfaceChange(
+            AF_UNSPEC,
+            Some(interface_changed),
+            Some(context),
+            false.into(),
+            &mut handle,
+        )
+    }
+    .ok()
+    .map_err(|e| format!("Failed to watch network interfaces: {}", e))?;
+
+    let mut handle = HANDLE::default();
+    unsafe {
+        NotifyUnicastIpAddressChange(
+            AF_UNSPEC,
+            Some(address_changed),
+            Some(context),
+            false.into(),
+            &mut handle,
+        )
+    }
+    .ok()
+    .map_err(|e| format!("Failed to watch network addresses: {}", e))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn watch(_tx: UnboundedSender<()>) -> Result<(), String> {
+    Err("No network change notifications on this platform".to_string())
+}
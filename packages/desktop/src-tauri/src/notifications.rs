@@ -0,0 +1,42 @@
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::windows::MainWindow;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationKind {
+    ServerDisconnected,
+}
+
+#[derive(Clone, Debug)]
+pub struct AppNotification {
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: Option<String>,
+}
+
+/// Shows a native notification unless the main window already has focus, in
+/// which case the frontend surfaces the event itself. The desktop plugin
+/// doesn't report clicks, so a notification only brings the user to the app;
+/// nothing is opened on their behalf.
+pub fn notify(app: &AppHandle, notification: AppNotification) -> Result<(), String> {
+    let focused = app
+        .get_webview_window(MainWindow::LABEL)
+        .and_then(|v| v.is_focused().ok())
+        .unwrap_or(false);
+    if focused {
+        return Ok(());
+    }
+
+    let mut builder = app.notification().builder().title(&notification.title);
+    if let Some(body) = &notification.body {
+        builder = builder.body(body);
+    }
+    builder
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))?;
+
+    tracing::debug!(kind = ?notification.kind, "Notification shown");
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use std::{collections::BTreeSet, sync::Mutex};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::constants::{PREVENT_SLEEP_KEY, SETTINGS_STORE};
+
+/// Keeps the machine awake while it is held. Every implementation also ends
+/// when the app exits, so a crash never leaves the machine unable to sleep.
+struct Inhibitor {
+    #[cfg(unix)]
+    child: std::process::Child,
+    #[cfg(windows)]
+    _release: std::sync::mpsc::Sender<()>,
+}
+
+impl Inhibitor {
+    #[cfg(target_os = "macos")]
+    fn acquire(_why: &str) -> Result<Self, String> {
+        // -i prevents idle sleep, -w ends the assertion when we exit.
+        let child = std::process::Command::new("caffeinate")
+            .args(["-i", "-w", &std::process::id().to_string()])
+            .spawn()
+            .map_err(|e| format!("Failed to run caffeinate: {}", e))?;
+
+        Ok(Self { child })
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn acquire(why: &str) -> Result<Self, String> {
+        // The lock is held for as long as the wrapped command runs, which
+        // `tail --pid` ties to our own lifetime.
+        let child = std::process::Command::new("systemd-inhibit")
+            .args([
+                "--what=idle:sleep",
+                "--who=OpenCode",
+                &format!("--why={why}"),
+                "--mode=block",
+                "tail",
+                &format!("--pid={}", std::process::id()),
+                "-f",
+                "/dev/null",
+            ])
+            .spawn()
+            .map_err(|e| format!("Failed to run systemd-inhibit: {}", e))?;
+
+        Ok(Self { child })
+    }
+
+    #[cfg(windows)]
+    fn acquire(_why: &str) -> Result<Self, String> {
+        use windows::Win32::System::Power::{
+            ES_CONTINUOUS, ES_SYSTEM_REQUIRED, SetThreadExecutionState,
+        };
+
+        // The execution state belongs to the thread that set it, so park a
+        // thread on the request until the sender is dropped.
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        std::thread::Builder::new()
+            .name("power-inhibit".to_string())
+            .spawn(move || unsafe {
+                SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED);
+                let _ = rx.recv();
+                SetThreadExecutionState(ES_CONTINUOUS);
+            })
+            .map_err(|e| format!("Failed to spawn power thread: {}", e))?;
+
+        Ok(Self { _release: tx })
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn acquire(_why: &str) -> Result<Self, String> {
+        Err("Sleep inhibition is not supported on this platform".to_string())
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+struct Inner {
+    enabled: bool,
+    reasons: BTreeSet<String>,
+    inhibitor: Option<Inhibitor>,
+}
+
+impl Inner {
+    fn sync(&mut self) {
+        let wanted = self.enabled && !self.reasons.is_empty();
+
+        if !wanted {
+            if self.inhibitor.take().is_some() {
+                tracing::info!("Released sleep inhibitor");
+            }
+            return;
+        }
+
+        if self.inhibitor.is_some() {
+            return;
+        }
+
+        let why = self.reasons.iter().cloned().collect::<Vec<_>>().join(", ");
+        match Inhibitor::acquire(&why) {
+            Ok(inhibitor) => {
+                tracing::info!(%why, "Acquired sleep inhibitor");
+                self.inhibitor = Some(inhibitor);
+            }
+            Err(e) => tracing::warn!("Failed to prevent sleep: {e}"),
+        }
+    }
+}
+
+/// Prevents the machine from sleeping while any reason (an active session, a
+/// remote build, ...) is held, unless the user opted out.
+pub struct PowerState(Mutex<Inner>);
+
+pub fn init(app: &AppHandle) {
+    app.manage(PowerState(Mutex::new(Inner {
+        enabled: prevent_sleep(app),
+        reasons: BTreeSet::new(),
+        inhibitor: None,
+    })));
+}
+
+pub fn prevent_sleep(app: &AppHandle) -> bool {
+    app.store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(PREVENT_SLEEP_KEY))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// Holds a sleep inhibitor for `reason` until `power_release` is called with
+/// the same reason. The frontend drives this from server busy/idle events.
+#[tauri::command]
+#[specta::specta]
+pub fn power_inhibit(app: AppHandle, reason: String) {
+    let state = app.state::<PowerState>();
+    let mut inner = state.0.lock().unwrap();
+    inner.reasons.insert(reason);
+    inner.sync();
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn power_release(app: AppHandle, reason: String) {
+    let state = app.state::<PowerState>();
+    let mut inner = state.0.lock().unwrap();
+    inner.reasons.remove(&reason);
+    inner.sync();
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_prevent_sleep(app: AppHandle) -> bool {
+    prevent_sleep(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_prevent_sleep(app: AppHandle, enabled: bool) -> Result<(), String> {
+    write_prevent_sleep(&app, enabled)
+}
+
+/// Saves the setting and takes or drops the inhibitor to match.
+pub fn write_prevent_sleep(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    store.set(PREVENT_SLEEP_KEY, serde_json::Value::Bool(enabled));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    let state = app.state::<PowerState>();
+    let mut inner = state.0.lock().unwrap();
+    inner.enabled = enabled;
+    inner.sync();
+
+    Ok(())
+}
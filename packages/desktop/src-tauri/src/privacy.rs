@@ -0,0 +1,376 @@
+use std::{io, path::Path, sync::RwLock};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::constants::{PRIVACY_MODE_KEY, SETTINGS_STORE};
+
+const PSEUDONYMS_FILE: &str = "pseudonyms.json";
+// Short user names would match unrelated words in log lines.
+const MIN_USER_LEN: usize = 3;
+const MAC_PLACEHOLDER: &str = "xx:xx:xx:xx:xx:xx";
+const MACHINE_ID_PLACEHOLDER: &str = "<machine-id>";
+
+static SCRUBBER: RwLock<Option<Scrubber>> = RwLock::new(None);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+struct Pseudonyms {
+    host: String,
+    user: String,
+}
+
+impl Pseudonyms {
+    fn generate() -> Self {
+        let id = || uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        Self {
+            host: format!("host-{}", id()),
+            user: format!("user-{}", id()),
+        }
+    }
+}
+
+/// Replaces the home directory, host and user names with stable, locally
+/// generated pseudonyms, and masks MAC addresses and the machine ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scrubber {
+    replacements: Vec<(String, String)>,
+}
+
+impl Scrubber {
+    fn new(
+        host: Option<String>,
+        user: Option<String>,
+        home: Option<String>,
+        machine_id: Option<String>,
+        names: &Pseudonyms,
+    ) -> Self {
+        // Longest first, so the home directory is replaced before the user name inside it.
+        let mut replacements = [
+            (home, "~".to_string()),
+            (host, names.host.clone()),
+            (user.filter(|v| v.len() >= MIN_USER_LEN), names.user.clone()),
+            (machine_id, MACHINE_ID_PLACEHOLDER.to_string()),
+        ]
+        .into_iter()
+        .filter_map(|(from, to)| Some((from.filter(|v| !v.is_empty())?, to)))
+        .collect::<Vec<_>>();
+        replacements.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+
+        Self { replacements }
+    }
+
+    pub fn scrub(&self, input: &str) -> String {
+        let scrubbed = self
+            .replacements
+            .iter()
+            .fold(input.to_string(), |acc, (from, to)| {
+                replace_word(&acc, from, to)
+            });
+        mask_macs(&scrubbed)
+    }
+}
+
+fn is_word(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Replaces `from` only where it isn't part of a longer word, so a user
+/// named "dev" leaves "device" alone but still matches "/home/dev/".
+fn replace_word(input: &str, from: &str, to: &str) -> String {
+    let (first, last) = (from.chars().next(), from.chars().next_back());
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    let mut prev = None;
+
+    while let Some(i) = rest.find(from) {
+        let before = rest[..i].chars().next_back().or(prev);
+        let after = rest[i + from.len()..].chars().next();
+        let bounded = !(is_word(before) && is_word(first)) && !(is_word(after) && is_word(last));
+
+        out.push_str(&rest[..i]);
+        out.push_str(if bounded { to } else { from });
+        prev = last;
+        rest = &rest[i + from.len()..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+// Six hex pairs joined by the same ':' or '-', e.g. 3c:22:fb:01:9a:e4.
+fn is_mac(window: &[u8]) -> bool {
+    let sep = window[2];
+    (sep == b':' || sep == b'-')
+        && window.iter().enumerate().all(|(i, b)| match i % 3 {
+            2 => *b == sep,
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+fn mask_macs(input: &str) -> String {
+    const LEN: usize = 17;
+    let bytes = input.as_bytes();
+    let joined = |b: Option<&u8>| b.is_some_and(|b| b.is_ascii_alphanumeric() || b"-:".contains(b));
+
+    let mut out = String::with_capacity(input.len());
+    let (mut start, mut i) = (0, 0);
+    while i + LEN <= bytes.len() {
+        if is_mac(&bytes[i..i + LEN])
+            && !joined(i.checked_sub(1).map(|j| &bytes[j]))
+            && !joined(bytes.get(i + LEN))
+        {
+            // Every byte of a match is ASCII, so both ends are char boundaries.
+            out.push_str(&input[start..i]);
+            out.push_str(MAC_PLACEHOLDER);
+            i += LEN;
+            start = i;
+        } else {
+            i += 1;
+        }
+    }
+
+    out.push_str(&input[start..]);
+    out
+}
+
+/// The OS's persistent machine identifier, if it can be read.
+fn machine_id() -> Option<String> {
+    #[cfg(target_os = "linux")]
+    let id = ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .into_iter()
+        .find_map(|path| std::fs::read_to_string(path).ok());
+
+    #[cfg(target_os = "macos")]
+    let id = std::process::Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .find(|v| v.contains("\"IOPlatformUUID\""))
+                .and_then(|v| v.rsplit('"').nth(1).map(str::to_string))
+        });
+
+    #[cfg(windows)]
+    let id = std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKLM\SOFTWARE\Microsoft\Cryptography",
+            "/v",
+            "MachineGuid",
+        ])
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .find(|v| v.contains("MachineGuid"))
+                .and_then(|v| v.split_whitespace().last().map(str::to_string))
+        });
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+    let id: Option<String> = None;
+
+    id.map(|v| v.trim().to_string())
+}
+
+pub fn scrub(input: &str) -> String {
+    match SCRUBBER.read().unwrap().as_ref() {
+        Some(scrubber) => scrubber.scrub(input),
+        None => input.to_string(),
+    }
+}
+
+/// Enables scrubbing if privacy mode was turned on in a previous run. Reads the
+/// settings file directly so it can run before logging and the store plugin.
+pub fn init(app: &AppHandle) {
+    let Ok(dir) = app.path().app_data_dir() else {
+        return;
+    };
+
+    let enabled = std::fs::read_to_string(dir.join(SETTINGS_STORE))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .and_then(|v| v.get(PRIVACY_MODE_KEY)?.as_bool())
+        .unwrap_or(false);
+
+    if enabled {
+        enable(&dir);
+    }
+}
+
+fn enable(dir: &Path) {
+    let scrubber = Scrubber::new(
+        Some(tauri_plugin_os::hostname()),
+        std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .ok(),
+        dirs::home_dir().map(|v| v.to_string_lossy().to_string()),
+        machine_id(),
+        &pseudonyms(dir),
+    );
+
+    *SCRUBBER.write().unwrap() = Some(scrubber);
+}
+
+fn pseudonyms(dir: &Path) -> Pseudonyms {
+    let path = dir.join(PSEUDONYMS_FILE);
+
+    if let Some(names) = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Pseudonyms>(&raw).ok())
+    {
+        return names;
+    }
+
+    let names = Pseudonyms::generate();
+    let _ = std::fs::create_dir_all(dir);
+    if let Ok(json) = serde_json::to_string(&names) {
+        let _ = std::fs::write(&path, json);
+    }
+    names
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_privacy_mode() -> bool {
+    SCRUBBER.read().unwrap().is_some()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_privacy_mode(app: AppHandle, enabled: bool) -> Result<(), String> {
+    write_privacy_mode(&app, enabled)
+}
+
+/// Saves the setting and starts or stops scrubbing right away.
+pub fn write_privacy_mode(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    store.set(PRIVACY_MODE_KEY, serde_json::Value::Bool(enabled));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    if enabled {
+        let dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+        enable(&dir);
+    } else {
+        *SCRUBBER.write().unwrap() = None;
+    }
+
+    tracing::info!(enabled, "Privacy mode changed");
+
+    Ok(())
+}
+
+/// Log writer wrapper that passes every formatted line through `scrub`.
+pub struct Scrubbed<M>(pub M);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Scrubbed<M> {
+    type Writer = ScrubbedWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        ScrubbedWriter(self.0.make_writer())
+    }
+}
+
+pub struct ScrubbedWriter<W>(W);
+
+impl<W: io::Write> io::Write for ScrubbedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !get_privacy_mode() {
+            return self.0.write(buf);
+        }
+
+        // The fmt layer writes each event as a single buffer, so lines are never split.
+        let Ok(line) = std::str::from_utf8(buf) else {
+            return self.0.write(buf);
+        };
+
+        self.0.write_all(scrub(line).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names() -> Pseudonyms {
+        Pseudonyms {
+            host: "host-1".to_string(),
+            user: "user-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn replaces_home_host_and_user() {
+        let scrubber = Scrubber::new(
+            Some("alice-mbp".to_string()),
+            Some("alice".to_string()),
+            Some("/Users/alice".to_string()),
+            None,
+            &names(),
+        );
+
+        assert_eq!(
+            scrubber.scrub("alice on alice-mbp opened /Users/alice/project"),
+            "user-1 on host-1 opened ~/project"
+        );
+    }
+
+    #[test]
+    fn skips_short_and_missing_identifiers() {
+        let scrubber = Scrubber::new(
+            None,
+            Some("al".to_string()),
+            Some(String::new()),
+            None,
+            &names(),
+        );
+
+        assert_eq!(scrubber.scrub("al was here"), "al was here");
+    }
+
+    #[test]
+    fn matches_user_name_only_as_a_whole_word() {
+        let scrubber = Scrubber::new(
+            None,
+            Some("dev".to_string()),
+            Some("/home/dev".to_string()),
+            None,
+            &names(),
+        );
+
+        assert_eq!(
+            scrubber.scrub("dev plugged in a device, see /home/dev/x and /home/devops"),
+            "user-1 plugged in a device, see ~/x and /home/devops"
+        );
+    }
+
+    #[test]
+    fn masks_mac_addresses_and_machine_id() {
+        let scrubber = Scrubber::new(None, None, None, Some("4c4c4544003a".to_string()), &names());
+
+        assert_eq!(
+            scrubber.scrub("en0 3c:22:fb:01:9A:e4, wlan 3C-22-FB-01-9A-E5, id 4c4c4544003a"),
+            "en0 xx:xx:xx:xx:xx:xx, wlan xx:xx:xx:xx:xx:xx, id <machine-id>"
+        );
+        // Longer colon-separated runs (IPv6, hashes) are left alone.
+        assert_eq!(
+            scrubber.scrub("fe80:3c:22:fb:01:9a:e4"),
+            "fe80:3c:22:fb:01:9a:e4"
+        );
+    }
+}
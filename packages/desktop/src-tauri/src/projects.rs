@@ -0,0 +1,231 @@
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_store::StoreExt;
+
+use crate::{
+    constants::{RECENT_PROJECTS_KEY, SETTINGS_STORE},
+    server,
+};
+
+// Pinned projects are kept regardless of this limit.
+const MAX_RECENT: usize = 20;
+
+/// A project directory and the host it lives on (`None` for this machine).
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct ProjectLocation {
+    pub path: String,
+    pub host: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct RecentProject {
+    pub path: String,
+    pub host: Option<String>,
+    pub last_opened: String,
+    pub pinned: bool,
+}
+
+impl RecentProject {
+    fn is(&self, location: &ProjectLocation) -> bool {
+        self.path == location.path && self.host == location.host
+    }
+}
+
+/// Shows the native folder picker and returns the chosen project, or `None`
+/// if the user cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn pick_project(app: AppHandle) -> Result<Option<ProjectLocation>, String> {
+    let picked = tokio::task::spawn_blocking(move || {
+        app.dialog()
+            .file()
+            .set_title("Open Project")
+            .blocking_pick_folder()
+    })
+    .await
+    .map_err(|e| format!("Failed to show folder picker: {}", e))?;
+
+    let Some(picked) = picked else {
+        return Ok(None);
+    };
+
+    let path = picked
+        .into_path()
+        .map_err(|e| format!("Failed to resolve picked folder: {}", e))?;
+
+    Ok(Some(ProjectLocation {
+        path: server::canonical_directory(&path.to_string_lossy())?,
+        host: None,
+    }))
+}
+
+pub fn load(app: &AppHandle) -> Result<Vec<RecentProject>, String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    Ok(store
+        .get(RECENT_PROJECTS_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn save(app: &AppHandle, projects: &[RecentProject]) -> Result<(), String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    let value = serde_json::to_value(projects)
+        .map_err(|e| format!("Failed to serialize recent projects: {}", e))?;
+    store.set(RECENT_PROJECTS_KEY, value);
+
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+// Pinned first, then most recently opened. RFC 3339 UTC timestamps sort
+// chronologically as strings.
+fn sort(projects: &mut Vec<RecentProject>) {
+    projects.sort_by(|a, b| {
+        b.pinned
+            .cmp(&a.pinned)
+            .then_with(|| b.last_opened.cmp(&a.last_opened))
+    });
+
+    let mut unpinned = 0;
+    projects.retain(|v| {
+        if !v.pinned {
+            unpinned += 1;
+        }
+        v.pinned || unpinned <= MAX_RECENT
+    });
+}
+
+fn touch(projects: &mut Vec<RecentProject>, location: ProjectLocation, now: String) {
+    match projects.iter_mut().find(|v| v.is(&location)) {
+        Some(project) => project.last_opened = now,
+        None => projects.push(RecentProject {
+            path: location.path,
+            host: location.host,
+            last_opened: now,
+            pinned: false,
+        }),
+    }
+    sort(projects);
+}
+
+/// Records that a project was opened, moving it to the top of the list.
+pub fn record(app: &AppHandle, location: ProjectLocation) -> Result<(), String> {
+    let mut projects = load(app)?;
+    touch(&mut projects, location, chrono::Utc::now().to_rfc3339());
+    save(app, &projects)
+}
+
+/// Whether `location` is a recent project or a directory inside one.
+pub fn contains(app: &AppHandle, location: &ProjectLocation) -> bool {
+    load(app).is_ok_and(|projects| {
+        projects.iter().any(|v| {
+            v.host == location.host && std::path::Path::new(&location.path).starts_with(&v.path)
+        })
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_recent_projects(app: AppHandle) -> Result<Vec<RecentProject>, String> {
+    let mut projects = load(&app)?;
+    sort(&mut projects);
+    Ok(projects)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_recent_project(app: AppHandle, location: ProjectLocation) -> Result<(), String> {
+    record(&app, location)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn pin_recent_project(
+    app: AppHandle,
+    location: ProjectLocation,
+    pinned: bool,
+) -> Result<(), String> {
+    let mut projects = load(&app)?;
+    let project = projects
+        .iter_mut()
+        .find(|v| v.is(&location))
+        .ok_or_else(|| format!("Not a recent project: {}", location.path))?;
+    project.pinned = pinned;
+
+    sort(&mut projects);
+    save(&app, &projects)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_recent_project(app: AppHandle, location: ProjectLocation) -> Result<(), String> {
+    let mut projects = load(&app)?;
+    projects.retain(|v| !v.is(&location));
+    save(&app, &projects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(path: &str) -> ProjectLocation {
+        ProjectLocation {
+            path: path.to_string(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn touch_moves_project_to_top() {
+        let mut projects = Vec::new();
+        touch(&mut projects, location("/a"), "2026-01-01T00:00:00Z".into());
+        touch(&mut projects, location("/b"), "2026-01-02T00:00:00Z".into());
+        touch(&mut projects, location("/a"), "2026-01-03T00:00:00Z".into());
+
+        let paths = projects.iter().map(|v| v.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths, ["/a", "/b"]);
+    }
+
+    #[test]
+    fn same_path_on_other_host_is_separate() {
+        let mut projects = Vec::new();
+        touch(&mut projects, location("/a"), "2026-01-01T00:00:00Z".into());
+        touch(
+            &mut projects,
+            ProjectLocation {
+                path: "/a".into(),
+                host: Some("devbox".into()),
+            },
+            "2026-01-02T00:00:00Z".into(),
+        );
+        assert_eq!(projects.len(), 2);
+    }
+
+    #[test]
+    fn pinned_projects_survive_the_limit() {
+        let mut projects = vec![RecentProject {
+            path: "/pinned".into(),
+            host: None,
+            last_opened: "2000-01-01T00:00:00Z".into(),
+            pinned: true,
+        }];
+        for i in 0..MAX_RECENT + 5 {
+            touch(
+                &mut projects,
+                location(&format!("/p{i}")),
+                format!("2026-01-01T00:00:{i:02}Z"),
+            );
+        }
+
+        assert_eq!(projects.len(), MAX_RECENT + 1);
+        assert_eq!(projects[0].path, "/pinned");
+        assert_eq!(projects[1].path, format!("/p{}", MAX_RECENT + 4));
+    }
+}
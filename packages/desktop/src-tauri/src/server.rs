@@ -1,21 +1,143 @@
-use std::time::{Duration, Instant};
+use std::{
+    collections::HashMap,
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogResult};
 use tauri_plugin_store::StoreExt;
-use tokio::task::JoinHandle;
+use tauri_specta::Event;
+use tokio::{sync::oneshot, task::JoinHandle, time::timeout};
 
 use crate::{
     cli,
-    cli::CommandChild,
-    constants::{DEFAULT_SERVER_URL_KEY, SETTINGS_STORE, WSL_ENABLED_KEY},
+    cli::{CommandChild, ServeExit},
+    constants::{DEFAULT_SERVER_URL_KEY, LOCAL_SERVER_KEY, SETTINGS_STORE, WSL_ENABLED_KEY},
+    health::HealthMonitor,
+    projects::ProjectLocation,
+    proxy,
+    state_file::{Connection, StateFile},
+    trust,
 };
 
+const MAX_RESTARTS: u32 = 5;
+const RESTART_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+const RESTART_TIMEOUT: Duration = Duration::from_secs(30);
+// A server that stays up this long is considered recovered and gets a fresh
+// restart budget.
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(tauri_specta::Event, serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+pub struct ServerCrashed {
+    pub directory: Option<String>,
+    pub code: Option<i32>,
+    pub signal: Option<i32>,
+    pub logs: Vec<String>,
+    pub restarting: bool,
+}
+
+#[derive(tauri_specta::Event, serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+pub struct ServerRestarted {
+    pub directory: Option<String>,
+    pub attempt: u32,
+}
+
 #[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type, Debug, Default)]
 pub struct WslConfig {
     pub enabled: bool,
 }
 
+/// How the locally spawned server listens. Changes apply the next time it starts.
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type, Debug, Default, PartialEq)]
+pub struct LocalServerConfig {
+    /// Bind address, loopback when unset.
+    pub hostname: Option<String>,
+    /// Fixed port, a free one is picked when unset.
+    pub port: Option<u32>,
+    /// Binds every interface so other devices can connect. The server still
+    /// requires the generated password.
+    pub lan_access: bool,
+}
+
+impl LocalServerConfig {
+    pub fn bind_hostname(&self) -> String {
+        if self.lan_access {
+            return "0.0.0.0".to_string();
+        }
+
+        self.hostname
+            .clone()
+            .unwrap_or_else(|| "127.0.0.1".to_string())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(port) = self.port
+            && !(1..=65535).contains(&port)
+        {
+            return Err(format!("Invalid port: {port}"));
+        }
+
+        if let Some(hostname) = &self.hostname
+            && !self.lan_access
+        {
+            let loopback = hostname.eq_ignore_ascii_case("localhost")
+                || hostname
+                    .trim_matches(['[', ']'])
+                    .parse::<std::net::IpAddr>()
+                    .is_ok_and(|ip| ip.is_loopback());
+
+            if !loopback {
+                return Err(format!(
+                    "Binding to {hostname} exposes the server to other devices, enable LAN access first"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The saved host and port for the local server.
+pub fn local_server_config(app: &AppHandle) -> Result<LocalServerConfig, String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    Ok(store
+        .get(LOCAL_SERVER_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_local_server_config(app: AppHandle) -> Result<LocalServerConfig, String> {
+    local_server_config(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_local_server_config(app: AppHandle, config: LocalServerConfig) -> Result<(), String> {
+    config.validate()?;
+
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    let value = serde_json::to_value(&config)
+        .map_err(|e| format!("Failed to serialize server config: {}", e))?;
+    store.set(LOCAL_SERVER_KEY, value);
+
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_default_server_url(app: AppHandle) -> Result<Option<String>, String> {
@@ -33,6 +155,10 @@ pub fn get_default_server_url(app: AppHandle) -> Result<Option<String>, String>
 #[tauri::command]
 #[specta::specta]
 pub async fn set_default_server_url(app: AppHandle, url: Option<String>) -> Result<(), String> {
+    write_default_server_url(&app, url)
+}
+
+pub fn write_default_server_url(app: &AppHandle, url: Option<String>) -> Result<(), String> {
     let store = app
         .store(SETTINGS_STORE)
         .map_err(|e| format!("Failed to open settings store: {}", e))?;
@@ -106,11 +232,12 @@ pub fn spawn_local_server(
     hostname: String,
     port: u32,
     password: String,
-) -> (CommandChild, HealthCheck) {
-    let (child, exit) = cli::serve(&app, &hostname, port, &password);
+    directory: Option<String>,
+) -> Result<(CommandChild, HealthCheck), String> {
+    let (child, mut exit) = cli::serve(&app, &hostname, port, &password, directory.as_deref())?;
 
     let health_check = HealthCheck(tokio::spawn(async move {
-        let url = format!("http://{hostname}:{port}");
+        let url = local_url(&hostname, port);
         let timestamp = Instant::now();
 
         let ready = async {
@@ -125,10 +252,10 @@ pub fn spawn_local_server(
         };
 
         let terminated = async {
-            match exit.await {
-                Ok(payload) => Err(format!(
+            match (&mut exit).await {
+                Ok(exit) => Err(format!(
                     "Sidecar terminated before becoming healthy (code={:?} signal={:?})",
-                    payload.code, payload.signal
+                    exit.payload.code, exit.payload.signal
                 )),
                 Err(_) => Err("Sidecar terminated before becoming healthy".to_string()),
             }
@@ -137,55 +264,551 @@ pub fn spawn_local_server(
         tokio::select! {
             res = ready => res,
             res = terminated => res,
-        }
+        }?;
+
+        Ok(Supervisor {
+            app,
+            hostname,
+            port,
+            password,
+            directory,
+            exit,
+        })
     }));
 
-    (child, health_check)
+    Ok((child, health_check))
 }
 
-pub struct HealthCheck(pub JoinHandle<Result<(), String>>);
+pub struct HealthCheck(pub JoinHandle<Result<Supervisor, String>>);
 
-pub async fn check_health(url: &str, password: Option<&str>) -> bool {
-    let Ok(url) = reqwest::Url::parse(url) else {
-        return false;
+/// Keeps a healthy local server running: when it exits without being asked to,
+/// it is respawned on the same port and password so the frontend's connection
+/// details stay valid.
+pub struct Supervisor {
+    app: AppHandle,
+    hostname: String,
+    port: u32,
+    password: String,
+    directory: Option<String>,
+    exit: oneshot::Receiver<ServeExit>,
+}
+
+impl Supervisor {
+    /// Runs until the server is stopped on purpose, which `kill_sidecar` signals
+    /// by taking the child out of `child`, or until the restart budget runs out.
+    pub async fn run(self, child: Arc<Mutex<Option<CommandChild>>>) {
+        let Self {
+            app,
+            hostname,
+            port,
+            password,
+            directory,
+            mut exit,
+        } = self;
+        let stopped = || child.lock().unwrap().is_none();
+        let mut attempt = 0;
+
+        loop {
+            let started = Instant::now();
+            let res = exit.await.ok();
+
+            if stopped() {
+                return;
+            }
+
+            if started.elapsed() >= STABLE_AFTER {
+                attempt = 0;
+            }
+
+            let (code, signal, logs) = match res {
+                Some(v) => (v.payload.code, v.payload.signal, v.stderr),
+                None => (None, None, Vec::new()),
+            };
+            tracing::error!(
+                ?code,
+                ?signal,
+                ?directory,
+                "Local server exited unexpectedly"
+            );
+
+            let restarting = attempt < MAX_RESTARTS;
+            let _ = ServerCrashed {
+                directory: directory.clone(),
+                code,
+                signal,
+                logs,
+                restarting,
+            }
+            .emit(&app);
+
+            exit = loop {
+                if attempt >= MAX_RESTARTS {
+                    tracing::error!(attempt, "Giving up on restarting local server");
+                    child.lock().unwrap().take();
+                    return;
+                }
+                attempt += 1;
+
+                tokio::time::sleep(backoff(attempt)).await;
+                if stopped() {
+                    return;
+                }
+
+                tracing::info!(attempt, "Restarting local server");
+                match restart(&app, &hostname, port, &password, directory.as_deref()).await {
+                    Ok((new_child, exit)) => {
+                        let mut slot = child.lock().unwrap();
+                        if slot.is_none() {
+                            let _ = new_child.kill();
+                            return;
+                        }
+                        *slot = Some(new_child);
+                        break exit;
+                    }
+                    Err(e) => tracing::warn!(attempt, "Failed to restart local server: {e}"),
+                }
+            };
+
+            let _ = ServerRestarted {
+                directory: directory.clone(),
+                attempt,
+            }
+            .emit(&app);
+        }
+    }
+}
+
+async fn restart(
+    app: &AppHandle,
+    hostname: &str,
+    port: u32,
+    password: &str,
+    directory: Option<&str>,
+) -> Result<(CommandChild, oneshot::Receiver<ServeExit>), String> {
+    // The port is kept so the frontend's connection details stay valid, but
+    // something else may have taken it since the crash.
+    match port_owner(port) {
+        PortOwner::Free => {}
+        PortOwner::Opencode { pids } => {
+            return Err(format!("Port {port} is in use by OpenCode (pid {pids:?})"));
+        }
+        PortOwner::Other { name } => return Err(format!("Port {port} is in use by {name}")),
+    }
+
+    let (child, health_check) = spawn_local_server(
+        app.clone(),
+        hostname.to_string(),
+        port,
+        password.to_string(),
+        directory.map(str::to_string),
+    )?;
+
+    await_health_check(child, health_check, RESTART_TIMEOUT)
+        .await
+        .map(|(child, supervisor)| (child, supervisor.exit))
+}
+
+/// Waits for a freshly spawned server to become healthy, killing it otherwise.
+async fn await_health_check(
+    child: CommandChild,
+    health_check: HealthCheck,
+    limit: Duration,
+) -> Result<(CommandChild, Supervisor), String> {
+    let res = match timeout(limit, health_check.0).await {
+        Ok(Ok(res)) => res,
+        Ok(Err(e)) => Err(format!("Health check task failed: {e}")),
+        Err(_) => Err("Health check timed out".to_string()),
+    };
+
+    match res {
+        Ok(supervisor) => Ok((child, supervisor)),
+        Err(e) => {
+            let _ = child.kill();
+            Err(e)
+        }
+    }
+}
+
+fn backoff(attempt: u32) -> Duration {
+    RESTART_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX_RESTART_BACKOFF)
+}
+
+/// A local server dedicated to a single project directory.
+#[derive(Clone, serde::Serialize, specta::Type, Debug)]
+pub struct ProjectServer {
+    pub directory: String,
+    pub url: String,
+    pub username: String,
+    pub password: String,
+}
+
+struct ProjectEntry {
+    server: ProjectServer,
+    child: Arc<Mutex<Option<CommandChild>>>,
+}
+
+impl ProjectEntry {
+    fn running(&self) -> bool {
+        self.child.lock().unwrap().is_some()
+    }
+
+    fn stop(&self) {
+        if let Some(child) = self.child.lock().unwrap().take() {
+            let _ = child.kill();
+        }
+    }
+}
+
+/// Local servers started per project window, keyed by canonical directory.
+#[derive(Default)]
+pub struct ProjectServers(Mutex<HashMap<String, ProjectEntry>>);
+
+impl ProjectServers {
+    pub fn stop_all(&self) {
+        for (_, entry) in self.0.lock().unwrap().drain() {
+            entry.stop();
+        }
+    }
+
+    fn get(&self, directory: &str) -> Option<ProjectServer> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(directory)
+            .filter(|v| v.running())
+            .map(|v| v.server.clone())
+    }
+}
+
+pub fn canonical_directory(directory: &str) -> Result<String, String> {
+    let path = std::fs::canonicalize(directory)
+        .map_err(|e| format!("Failed to resolve directory: {}", e))?;
+
+    if !path.is_dir() {
+        return Err(format!("Not a directory: {}", path.display()));
+    }
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+fn free_port() -> Result<u32, String> {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|v| v.local_addr())
+        .map(|v| v.port() as u32)
+        .map_err(|e| format!("Failed to find free port: {}", e))
+}
+
+/// Who is listening on a port the local server wants to bind.
+#[derive(Debug, PartialEq)]
+pub enum PortOwner {
+    Free,
+    /// A leftover opencode server, e.g. from a previous crash.
+    Opencode {
+        pids: Vec<u32>,
+    },
+    Other {
+        name: String,
+    },
+}
+
+pub fn port_owner(port: u32) -> PortOwner {
+    let Ok(port) = u16::try_from(port) else {
+        return PortOwner::Free;
     };
 
-    let mut builder = reqwest::Client::builder().timeout(Duration::from_secs(7));
+    match listeners::get_processes_by_port(port) {
+        Ok(processes) => {
+            classify_port_owner(processes.into_iter().map(|v| (v.pid, v.name)).collect())
+        }
+        Err(e) => {
+            tracing::debug!(port, "Failed to list port listeners: {e}");
+            PortOwner::Free
+        }
+    }
+}
+
+fn classify_port_owner(mut processes: Vec<(u32, String)>) -> PortOwner {
+    if processes.is_empty() {
+        return PortOwner::Free;
+    }
+    processes.sort();
+
+    if processes
+        .iter()
+        .all(|(_, name)| name.to_lowercase().contains("opencode"))
+    {
+        return PortOwner::Opencode {
+            pids: processes.into_iter().map(|(pid, _)| pid).collect(),
+        };
+    }
 
-    if url_is_localhost(&url) {
-        // Some environments set proxy variables (HTTP_PROXY/HTTPS_PROXY/ALL_PROXY) without
-        // excluding loopback. reqwest respects these by default, which can prevent the desktop
-        // app from reaching its own local sidecar server.
-        builder = builder.no_proxy();
+    PortOwner::Other {
+        name: processes
+            .into_iter()
+            .find(|(_, name)| !name.to_lowercase().contains("opencode"))
+            .map(|(_, name)| name)
+            .unwrap_or_default(),
+    }
+}
+
+fn kill_process(pid: u32) -> bool {
+    let status = if cfg!(windows) {
+        std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .status()
+    } else {
+        std::process::Command::new("kill")
+            .arg(pid.to_string())
+            .status()
     };
 
-    let Ok(client) = builder.build() else {
-        return false;
+    status.is_ok_and(|v| v.success())
+}
+
+/// Makes sure `port` can be bound before spawning the local server. A stale
+/// opencode server can be stopped after asking the user; otherwise, or when
+/// another program owns the port, a free port is used instead.
+pub async fn resolve_port_conflict(app: &AppHandle, port: u32) -> u32 {
+    let fallback = || {
+        free_port().unwrap_or_else(|e| {
+            tracing::error!("{e}");
+            port
+        })
     };
-    let Ok(health_url) = url.join("/global/health") else {
-        return false;
+
+    match port_owner(port) {
+        PortOwner::Free => port,
+        PortOwner::Other { name } => {
+            tracing::warn!(port, %name, "Port is in use by another program, using a free port");
+            fallback()
+        }
+        PortOwner::Opencode { pids } => {
+            tracing::warn!(port, ?pids, "Port is in use by a previous OpenCode server");
+
+            const STOP: &str = "Stop It";
+
+            let pid_list = pids
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let res = app
+                .dialog()
+                .message(format!(
+                    "Port {port} is in use by a previous OpenCode server (pid {pid_list}).\n\nStop it and start a fresh server, or use a different port?"
+                ))
+                .title("Port In Use")
+                .buttons(MessageDialogButtons::OkCancelCustom(
+                    STOP.to_string(),
+                    "Use Another Port".to_string(),
+                ))
+                .blocking_show_with_result();
+
+            if !matches!(res, MessageDialogResult::Custom(name) if name == STOP) {
+                return fallback();
+            }
+
+            for pid in &pids {
+                if !kill_process(*pid) {
+                    tracing::warn!(pid, "Failed to stop previous server");
+                }
+            }
+
+            for _ in 0..30 {
+                if port_owner(port) == PortOwner::Free {
+                    tracing::info!(port, "Previous server stopped");
+                    return port;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+
+            tracing::warn!(port, "Previous server still running, using a free port");
+            fallback()
+        }
+    }
+}
+
+/// Starts (or reuses) the sidecar serving `directory`.
+pub async fn start_project(app: &AppHandle, directory: &str) -> Result<ProjectServer, String> {
+    let directory = canonical_directory(directory)?;
+    trust::require(
+        app,
+        &ProjectLocation {
+            path: directory.clone(),
+            host: None,
+        },
+    )?;
+
+    let servers = app.state::<ProjectServers>();
+
+    if let Some(server) = servers.get(&directory) {
+        return Ok(server);
+    }
+
+    let hostname = "127.0.0.1";
+    let port = free_port()?;
+    let password = uuid::Uuid::new_v4().to_string();
+
+    tracing::info!(%directory, port, "Starting project server");
+    let (child, health_check) = spawn_local_server(
+        app.clone(),
+        hostname.to_string(),
+        port,
+        password.clone(),
+        Some(directory.clone()),
+    )?;
+    let (child, supervisor) = await_health_check(child, health_check, RESTART_TIMEOUT).await?;
+
+    let server = ProjectServer {
+        directory: directory.clone(),
+        url: local_url(hostname, port),
+        username: "opencode".to_string(),
+        password,
     };
 
+    let mut entries = servers.0.lock().unwrap();
+    // Another window may have started a server for the same project meanwhile.
+    if let Some(existing) = entries.get(&directory).filter(|v| v.running()) {
+        let _ = child.kill();
+        return Ok(existing.server.clone());
+    }
+
+    let slot = Arc::new(Mutex::new(Some(child)));
+    tokio::spawn(supervisor.run(slot.clone()));
+    entries.insert(
+        directory.clone(),
+        ProjectEntry {
+            server: server.clone(),
+            child: slot,
+        },
+    );
+
+    app.state::<HealthMonitor>().watch(
+        server.url.clone(),
+        Some(server.password.clone()),
+        Some(directory.clone()),
+    );
+
+    if let Some(state) = app.try_state::<StateFile>() {
+        state.add(Connection {
+            url: server.url.clone(),
+            sidecar: true,
+            destination: None,
+            directory: Some(directory),
+        });
+    }
+
+    Ok(server)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn start_project_server(
+    app: AppHandle,
+    directory: String,
+) -> Result<ProjectServer, String> {
+    start_project(&app, &directory).await
+}
+
+/// Stops the sidecar serving `directory`, if any.
+pub fn stop_project(app: &AppHandle, directory: &str) -> Result<(), String> {
+    let directory = canonical_directory(directory)?;
+
+    let Some(entry) = app
+        .state::<ProjectServers>()
+        .0
+        .lock()
+        .unwrap()
+        .remove(&directory)
+    else {
+        return Ok(());
+    };
+
+    entry.stop();
+    tracing::info!(%directory, "Stopped project server");
+
+    app.state::<HealthMonitor>().unwatch(&entry.server.url);
+
+    if let Some(state) = app.try_state::<StateFile>() {
+        state.remove(&entry.server.url);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn stop_project_server(app: AppHandle, directory: String) -> Result<(), String> {
+    stop_project(&app, &directory)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_project_servers(app: AppHandle) -> Vec<ProjectServer> {
+    app.state::<ProjectServers>()
+        .0
+        .lock()
+        .unwrap()
+        .values()
+        .filter(|v| v.running())
+        .map(|v| v.server.clone())
+        .collect()
+}
+
+/// Body of `GET /global/health`. Older servers may not report a version.
+#[derive(serde::Deserialize, Default, Debug)]
+pub struct HealthResponse {
+    pub version: Option<String>,
+}
+
+pub async fn check_health(url: &str, password: Option<&str>) -> bool {
+    fetch_health(url, password).await.is_ok()
+}
+
+pub async fn fetch_health(url: &str, password: Option<&str>) -> Result<HealthResponse, String> {
+    let url = reqwest::Url::parse(url).map_err(|e| format!("Invalid server URL: {}", e))?;
+
+    let builder = proxy::client_builder(&url).timeout(Duration::from_secs(7));
+
+    let client = builder
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    let health_url = url
+        .join("/global/health")
+        .map_err(|e| format!("Invalid server URL: {}", e))?;
+
     let mut req = client.get(health_url);
 
     if let Some(password) = password {
         req = req.basic_auth("opencode", Some(password));
     }
 
-    req.send()
+    let res = req
+        .send()
         .await
-        .map(|r| r.status().is_success())
-        .unwrap_or(false)
+        .map_err(|e| format!("Health request failed: {}", e))?;
+
+    if !res.status().is_success() {
+        return Err(format!("Health check returned {}", res.status()));
+    }
+
+    let body = res.text().await.unwrap_or_default();
+    Ok(serde_json::from_str(&body).unwrap_or_default())
 }
 
 pub fn is_localhost_url(url: &str) -> bool {
     reqwest::Url::parse(url).is_ok_and(|u| url_is_localhost(&u))
 }
 
-fn url_is_localhost(url: &reqwest::Url) -> bool {
+pub fn url_is_localhost(url: &reqwest::Url) -> bool {
     url.host_str().is_some_and(|host| {
         host.eq_ignore_ascii_case("localhost")
             || host
+                // IPv6 hosts keep their brackets, e.g. "[::1]".
+                .trim_matches(['[', ']'])
                 .parse::<std::net::IpAddr>()
                 .is_ok_and(|ip| ip.is_loopback())
     })
@@ -194,7 +817,7 @@ fn url_is_localhost(url: &reqwest::Url) -> bool {
 /// Converts a bind address hostname to a valid URL hostname for connection.
 /// - `0.0.0.0` and `::` are wildcard bind addresses, not valid connect targets
 /// - IPv6 addresses need brackets in URLs (e.g., `::1` -> `[::1]`)
-fn normalize_hostname_for_url(hostname: &str) -> String {
+pub fn normalize_hostname_for_url(hostname: &str) -> String {
     // Wildcard bind addresses -> localhost equivalents
     if hostname == "0.0.0.0" {
         return "127.0.0.1".to_string();
@@ -211,6 +834,11 @@ fn normalize_hostname_for_url(hostname: &str) -> String {
     hostname.to_string()
 }
 
+/// URL for connecting to a local server bound to `hostname`.
+pub fn local_url(hostname: &str, port: u32) -> String {
+    format!("http://{}:{port}", normalize_hostname_for_url(hostname))
+}
+
 fn get_server_url_from_config(config: &cli::Config) -> Option<String> {
     let server = config.server.as_ref()?;
     let port = server.port?;
@@ -251,3 +879,88 @@ pub async fn check_health_or_ask_retry(app: &AppHandle, url: &str) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_url_connects_to_loopback() {
+        let url = local_url("::1", 4096);
+        assert_eq!(url, "http://[::1]:4096");
+        assert!(url_is_localhost(&reqwest::Url::parse(&url).unwrap()));
+
+        let lan = LocalServerConfig {
+            lan_access: true,
+            ..Default::default()
+        };
+        let url = local_url(&lan.bind_hostname(), 4096);
+        assert_eq!(url, "http://127.0.0.1:4096");
+        assert!(url_is_localhost(&reqwest::Url::parse(&url).unwrap()));
+    }
+
+    #[test]
+    fn lan_access_binds_all_interfaces() {
+        let config = LocalServerConfig {
+            hostname: Some("127.0.0.1".to_string()),
+            port: Some(4096),
+            lan_access: true,
+        };
+        assert_eq!(config.bind_hostname(), "0.0.0.0");
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(LocalServerConfig::default().bind_hostname(), "127.0.0.1");
+    }
+
+    #[test]
+    fn non_loopback_hostname_requires_lan_access() {
+        let config = |hostname: &str| LocalServerConfig {
+            hostname: Some(hostname.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(config("localhost").validate(), Ok(()));
+        assert_eq!(config("::1").validate(), Ok(()));
+        assert!(config("192.168.1.10").validate().is_err());
+        assert!(config("0.0.0.0").validate().is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_port() {
+        let config = |port| LocalServerConfig {
+            port: Some(port),
+            ..Default::default()
+        };
+        assert_eq!(config(4096).validate(), Ok(()));
+        assert!(config(0).validate().is_err());
+        assert!(config(70000).validate().is_err());
+    }
+
+    #[test]
+    fn classifies_port_owners() {
+        assert_eq!(classify_port_owner(Vec::new()), PortOwner::Free);
+        assert_eq!(
+            classify_port_owner(vec![
+                (20, "opencode-cli".to_string()),
+                (10, "opencode".to_string())
+            ]),
+            PortOwner::Opencode { pids: vec![10, 20] }
+        );
+        assert_eq!(
+            classify_port_owner(vec![
+                (10, "opencode-cli".to_string()),
+                (11, "node".to_string())
+            ]),
+            PortOwner::Other {
+                name: "node".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(2), Duration::from_secs(2));
+        assert_eq!(backoff(4), Duration::from_secs(8));
+        assert_eq!(backoff(10), MAX_RESTART_BACKOFF);
+        assert_eq!(backoff(u32::MAX), MAX_RESTART_BACKOFF);
+    }
+}
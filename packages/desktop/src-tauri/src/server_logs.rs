@@ -0,0 +1,271 @@
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+use tauri::{AppHandle, Manager, ipc::Channel};
+
+use crate::privacy;
+
+const FILE_NAME: &str = "server.log";
+const MAX_FILE_SIZE: u64 = 5 * 1024 * 1024;
+const MAX_ROTATED_FILES: usize = 3;
+const BUFFER_LINES: usize = 5000;
+
+#[derive(serde::Serialize, specta::Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(serde::Serialize, specta::Type, Clone, Debug)]
+pub struct ServerLogLine {
+    pub seq: u32,
+    pub timestamp: String,
+    pub stream: LogStream,
+    pub directory: Option<String>,
+    pub text: String,
+}
+
+impl ServerLogLine {
+    fn matches(&self, filter: Option<&str>) -> bool {
+        filter.is_none_or(|filter| self.text.to_lowercase().contains(&filter.to_lowercase()))
+    }
+}
+
+struct Follower {
+    id: u32,
+    filter: Option<String>,
+    channel: Channel<ServerLogLine>,
+}
+
+struct Inner {
+    seq: u32,
+    lines: VecDeque<ServerLogLine>,
+    file: Option<File>,
+    size: u64,
+    followers: Vec<Follower>,
+}
+
+/// Output of every local server process, kept in memory for the server console
+/// and mirrored to `<app log dir>/server.log` with size-based rotation.
+pub struct ServerLogs {
+    path: Option<PathBuf>,
+    inner: Mutex<Inner>,
+}
+
+impl ServerLogs {
+    pub fn init(app: &AppHandle) {
+        let path = app.path().app_log_dir().ok().map(|dir| dir.join(FILE_NAME));
+
+        let (file, size) = match path.as_deref().map(open) {
+            Some(Ok((file, size))) => (Some(file), size),
+            Some(Err(e)) => {
+                tracing::warn!("Failed to open server log: {e}");
+                (None, 0)
+            }
+            None => (None, 0),
+        };
+
+        app.manage(Self {
+            path,
+            inner: Mutex::new(Inner {
+                seq: 0,
+                lines: VecDeque::with_capacity(BUFFER_LINES),
+                file,
+                size,
+                followers: Vec::new(),
+            }),
+        });
+    }
+
+    pub fn push(&self, stream: LogStream, directory: Option<&str>, text: &str) {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.seq = inner.seq.wrapping_add(1);
+        let line = ServerLogLine {
+            seq: inner.seq,
+            timestamp: chrono::Local::now().to_rfc3339(),
+            stream,
+            directory: directory.map(str::to_string),
+            text: privacy::scrub(text),
+        };
+
+        self.write(&mut inner, &line);
+
+        inner
+            .followers
+            .retain(|v| !line.matches(v.filter.as_deref()) || v.channel.send(line.clone()).is_ok());
+
+        if inner.lines.len() == BUFFER_LINES {
+            inner.lines.pop_front();
+        }
+        inner.lines.push_back(line);
+    }
+
+    fn write(&self, inner: &mut Inner, line: &ServerLogLine) {
+        let entry = format!(
+            "{} {:?} {}{}\n",
+            line.timestamp,
+            line.stream,
+            line.directory
+                .as_deref()
+                .map(|v| format!("[{v}] "))
+                .unwrap_or_default(),
+            line.text
+        );
+
+        if let Some(path) = &self.path
+            && inner.size + entry.len() as u64 > MAX_FILE_SIZE
+        {
+            inner.file = None;
+            rotate(path);
+            match open(path) {
+                Ok((file, size)) => {
+                    inner.file = Some(file);
+                    inner.size = size;
+                }
+                Err(e) => tracing::warn!("Failed to reopen server log: {e}"),
+            }
+        }
+
+        if let Some(file) = inner.file.as_mut()
+            && file.write_all(entry.as_bytes()).is_ok()
+        {
+            inner.size += entry.len() as u64;
+        }
+    }
+}
+
+fn open(path: &Path) -> Result<(File, u64), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create log dir: {}", e))?;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open log file: {}", e))?;
+    let size = file.metadata().map(|v| v.len()).unwrap_or(0);
+
+    Ok((file, size))
+}
+
+fn rotated(path: &Path, index: usize) -> PathBuf {
+    path.with_extension(format!("log.{index}"))
+}
+
+// server.log -> server.log.1 -> ... -> server.log.N, dropping the oldest.
+fn rotate(path: &Path) {
+    let _ = std::fs::remove_file(rotated(path, MAX_ROTATED_FILES));
+    for index in (1..MAX_ROTATED_FILES).rev() {
+        let _ = std::fs::rename(rotated(path, index), rotated(path, index + 1));
+    }
+    let _ = std::fs::rename(path, rotated(path, 1));
+}
+
+/// Returns up to `lines` of the most recent buffered output, oldest first,
+/// optionally limited to lines containing `filter` (case-insensitive).
+#[tauri::command]
+#[specta::specta]
+pub fn server_logs(app: AppHandle, lines: u32, filter: Option<String>) -> Vec<ServerLogLine> {
+    let logs = app.state::<ServerLogs>();
+    let inner = logs.inner.lock().unwrap();
+
+    let mut matching = inner
+        .lines
+        .iter()
+        .rev()
+        .filter(|v| v.matches(filter.as_deref()))
+        .take(lines as usize)
+        .cloned()
+        .collect::<Vec<_>>();
+    matching.reverse();
+    matching
+}
+
+/// Streams new output lines to `events` until `server_logs_unfollow` is called
+/// with the returned id or the channel is closed.
+#[tauri::command]
+#[specta::specta]
+pub fn server_logs_follow(
+    app: AppHandle,
+    filter: Option<String>,
+    events: Channel<ServerLogLine>,
+) -> u32 {
+    let logs = app.state::<ServerLogs>();
+    let mut inner = logs.inner.lock().unwrap();
+
+    let id = inner.followers.iter().map(|v| v.id).max().unwrap_or(0) + 1;
+    inner.followers.push(Follower {
+        id,
+        filter,
+        channel: events,
+    });
+    id
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn server_logs_unfollow(app: AppHandle, id: u32) {
+    app.state::<ServerLogs>()
+        .inner
+        .lock()
+        .unwrap()
+        .followers
+        .retain(|v| v.id != id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> ServerLogLine {
+        ServerLogLine {
+            seq: 1,
+            timestamp: String::new(),
+            stream: LogStream::Stderr,
+            directory: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn filter_is_case_insensitive() {
+        assert!(line("ERROR failed to bind").matches(Some("error")));
+        assert!(line("anything").matches(None));
+        assert!(!line("INFO started").matches(Some("error")));
+    }
+
+    #[test]
+    fn rotate_shifts_files_and_drops_oldest() {
+        let dir =
+            std::env::temp_dir().join(format!("opencode-server-logs-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(FILE_NAME);
+
+        for index in 1..=MAX_ROTATED_FILES {
+            std::fs::write(rotated(&path, index), index.to_string()).unwrap();
+        }
+        std::fs::write(&path, "current").unwrap();
+
+        rotate(&path);
+
+        assert!(!path.exists());
+        assert_eq!(
+            std::fs::read_to_string(rotated(&path, 1)).unwrap(),
+            "current"
+        );
+        assert_eq!(std::fs::read_to_string(rotated(&path, 2)).unwrap(), "1");
+        assert_eq!(
+            std::fs::read_to_string(rotated(&path, MAX_ROTATED_FILES)).unwrap(),
+            (MAX_ROTATED_FILES - 1).to_string()
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
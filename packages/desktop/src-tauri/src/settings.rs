@@ -0,0 +1,253 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+use tauri_specta::Event;
+
+use crate::{
+    autostart,
+    constants::{DEFAULT_SERVER_URL_KEY, PRIVACY_MODE_KEY, SETTINGS_STORE},
+    power, privacy, server,
+};
+
+const RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinuxDecorations {
+    Auto,
+    Native,
+    None,
+}
+
+impl LinuxDecorations {
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Native => "native",
+            Self::None => "none",
+        }
+    }
+}
+
+/// Every app-level setting in one typed place. Each field is still stored
+/// under its own key, so the individual get/set commands keep working.
+///
+/// `prefer_wayland` and `linux_decorations` replace the `OC_ALLOW_WAYLAND`
+/// and `OC_LINUX_DECORATIONS` environment toggles (which still win when set)
+/// and take effect on the next launch.
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub default_server_url: Option<String>,
+    pub privacy_mode: bool,
+    pub background_mode: bool,
+    pub prevent_sleep: bool,
+    pub prefer_wayland: bool,
+    pub linux_decorations: Option<LinuxDecorations>,
+}
+
+#[derive(tauri_specta::Event, serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+pub struct SettingsChanged {
+    pub settings: Settings,
+}
+
+fn read(app: &AppHandle) -> Settings {
+    let store = app.store(SETTINGS_STORE).ok();
+    let get = |key: &str| store.as_ref().and_then(|store| store.get(key));
+
+    #[cfg(target_os = "linux")]
+    let (prefer_wayland, linux_decorations) = (
+        crate::linux_display::read_wayland().unwrap_or(false),
+        crate::linux_display::read_decorations().and_then(|v| {
+            serde_json::from_value::<LinuxDecorations>(serde_json::Value::String(v)).ok()
+        }),
+    );
+    #[cfg(not(target_os = "linux"))]
+    let (prefer_wayland, linux_decorations) = (false, None);
+
+    Settings {
+        default_server_url: get(DEFAULT_SERVER_URL_KEY).and_then(|v| v.as_str().map(String::from)),
+        privacy_mode: get(PRIVACY_MODE_KEY)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        background_mode: autostart::background_mode(app),
+        prevent_sleep: power::prevent_sleep(app),
+        prefer_wayland,
+        linux_decorations,
+    }
+}
+
+// Goes through the same writers as each setting's own command so their side
+// effects (privacy scrubbing, sleep inhibition, ...) apply too.
+fn apply(app: &AppHandle, old: &Settings, new: &Settings) -> Result<(), String> {
+    if new.default_server_url != old.default_server_url {
+        server::write_default_server_url(app, new.default_server_url.clone())?;
+    }
+    if new.privacy_mode != old.privacy_mode {
+        privacy::write_privacy_mode(app, new.privacy_mode)?;
+    }
+    if new.background_mode != old.background_mode {
+        autostart::write_background_mode(app, new.background_mode)?;
+    }
+    if new.prevent_sleep != old.prevent_sleep {
+        power::write_prevent_sleep(app, new.prevent_sleep)?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if new.prefer_wayland != old.prefer_wayland {
+            crate::linux_display::write_wayland(app, new.prefer_wayland)?;
+        }
+        if new.linux_decorations != old.linux_decorations {
+            crate::linux_display::write_decorations(
+                app,
+                new.linux_decorations.map(|v| v.as_str().to_string()),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Last settings sent to the frontend, to only emit real changes.
+struct Current(Mutex<Settings>);
+
+fn publish(app: &AppHandle, settings: Settings) {
+    let current = app.state::<Current>();
+    let mut current = current.0.lock().unwrap();
+    if *current != settings {
+        *current = settings.clone();
+        let _ = SettingsChanged { settings }.emit(app);
+    }
+}
+
+type Entries = serde_json::Map<String, serde_json::Value>;
+
+fn read_file(path: &std::path::Path) -> Option<Entries> {
+    serde_json::from_slice(&std::fs::read(path).ok()?).ok()
+}
+
+fn entries(store: &tauri_plugin_store::Store<tauri::Wry>) -> Entries {
+    store.entries().into_iter().collect()
+}
+
+/// The outside edits to copy into memory: keys that changed on disk since
+/// `last` while staying untouched in memory. Reloading the whole store instead
+/// would drop values a command has set but not saved yet, and a file that
+/// matches memory (our own save) yields nothing. `None` deletes the key.
+fn external_changes(
+    last: &Entries,
+    disk: &Entries,
+    memory: &Entries,
+) -> Vec<(String, Option<serde_json::Value>)> {
+    last.keys()
+        .chain(disk.keys().filter(|v| !last.contains_key(*v)))
+        .filter(|key| disk.get(*key) != last.get(*key))
+        .filter(|key| memory.get(*key) == last.get(*key))
+        .map(|key| (key.clone(), disk.get(key).cloned()))
+        .collect()
+}
+
+/// Tracks settings and picks up edits made to the settings file while the app
+/// is running.
+pub fn init(app: &AppHandle) {
+    app.manage(Current(Mutex::new(read(app))));
+
+    let Ok(path) = app.path().app_data_dir().map(|v| v.join(SETTINGS_STORE)) else {
+        return;
+    };
+    let modified = |path: &std::path::Path| -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|v| v.modified()).ok()
+    };
+
+    let Ok(store) = app.store(SETTINGS_STORE) else {
+        return;
+    };
+
+    let app = app.clone();
+    tokio::spawn(async move {
+        let mut last_modified = modified(&path);
+        let mut last = read_file(&path).unwrap_or_else(|| entries(&store));
+
+        loop {
+            tokio::time::sleep(RELOAD_INTERVAL).await;
+
+            let next = modified(&path);
+            if next == last_modified {
+                continue;
+            }
+
+            // Mid-write or hand-edited into invalid JSON; try again next tick.
+            let Some(disk) = read_file(&path) else {
+                continue;
+            };
+            last_modified = next;
+            for (key, value) in external_changes(&last, &disk, &entries(&store)) {
+                match value {
+                    Some(value) => store.set(key, value),
+                    None => {
+                        store.delete(key);
+                    }
+                }
+            }
+            last = disk;
+
+            // Compared with what was last applied, so runtime state such as
+            // privacy scrubbing follows edits made outside the app.
+            let old = app.state::<Current>().0.lock().unwrap().clone();
+            let new = read(&app);
+            if new != old {
+                tracing::info!("Settings changed on disk");
+                if let Err(e) = apply(&app, &old, &new) {
+                    tracing::warn!("Failed to apply reloaded settings: {e}");
+                }
+                publish(&app, new);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn settings_get(app: AppHandle) -> Settings {
+    read(&app)
+}
+
+/// Saves every changed field and notifies all windows with `SettingsChanged`.
+#[tauri::command]
+#[specta::specta]
+pub fn settings_set(app: AppHandle, settings: Settings) -> Result<(), String> {
+    let old = read(&app);
+    apply(&app, &old, &settings)?;
+    publish(&app, read(&app));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn map(value: serde_json::Value) -> Entries {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn external_changes_skip_own_and_unsaved_writes() {
+        let last = map(json!({ "a": 1, "b": 1, "c": 1 }));
+        // Our save of `a` landed, `b` was set since but isn't saved yet, and
+        // `c` and `d` were edited outside the app.
+        let disk = map(json!({ "a": 2, "b": 1, "d": 1 }));
+        let memory = map(json!({ "a": 2, "b": 2, "c": 1 }));
+
+        let mut changes = external_changes(&last, &disk, &memory);
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            changes,
+            [("c".to_string(), None), ("d".to_string(), Some(json!(1)))]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use tauri::AppHandle;
+
+use crate::{autostart, cli};
+
+#[cfg_attr(not(any(target_os = "linux", windows)), allow(dead_code))]
+const MENU_LABEL: &str = "Open in OpenCode";
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_shell_integration(app: AppHandle) -> bool {
+    is_installed(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn install_shell_integration(app: AppHandle) -> Result<(), String> {
+    install(&app, &autostart::executable()?)?;
+    tracing::info!("Installed shell integration");
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn uninstall_shell_integration(app: AppHandle) -> Result<(), String> {
+    uninstall(&app)?;
+    tracing::info!("Removed shell integration");
+    Ok(())
+}
+
+// File manager actions rather than an application entry for inode/directory,
+// which could become the default handler for opening folders. Dolphin reads
+// service menus and Nautilus reads scripts; both hand over plain paths, which
+// the app turns into OpenProject actions on launch.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn service_menu(exe: &str) -> String {
+    format!(
+        "[Desktop Entry]\nType=Service\nMimeType=inode/directory;\nActions=open;\nX-KDE-Priority=TopLevel\n\n[Desktop Action open]\nName={MENU_LABEL}\nExec={} %F\n",
+        autostart::quote_exec(exe)
+    )
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn nautilus_script(exe: &str) -> String {
+    format!("#!/bin/sh\nexec {} \"$@\"\n", cli::shell_escape(exe))
+}
+
+#[cfg(target_os = "linux")]
+fn entry_paths(app: &AppHandle) -> Option<[std::path::PathBuf; 2]> {
+    let data_dir = dirs::data_dir()?;
+    Some([
+        data_dir
+            .join("kio")
+            .join("servicemenus")
+            .join(format!("{}.desktop", app.config().identifier)),
+        data_dir.join("nautilus").join("scripts").join(MENU_LABEL),
+    ])
+}
+
+#[cfg(target_os = "linux")]
+fn is_installed(app: &AppHandle) -> bool {
+    entry_paths(app).is_some_and(|paths| paths.iter().any(|v| v.exists()))
+}
+
+#[cfg(target_os = "linux")]
+fn install(app: &AppHandle, exe: &str) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let paths = entry_paths(app).ok_or_else(|| "Could not resolve data dir".to_string())?;
+
+    // Dolphin ignores service menus in the user's data dir unless they are
+    // executable, and Nautilus only runs executable scripts.
+    for (path, contents) in paths.iter().zip([service_menu(exe), nautilus_script(exe)]) {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to make {} executable: {}", path.display(), e))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn uninstall(app: &AppHandle) -> Result<(), String> {
+    let Some(paths) = entry_paths(app) else {
+        return Ok(());
+    };
+
+    for path in paths {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(format!("Failed to remove {}: {}", path.display(), e));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Folders themselves and the background of an open folder.
+#[cfg(windows)]
+const VERB_KEYS: [&str; 2] = [
+    r"HKCU\Software\Classes\Directory\shell\OpenCode",
+    r"HKCU\Software\Classes\Directory\Background\shell\OpenCode",
+];
+
+#[cfg(windows)]
+fn reg(args: &[&str]) -> Result<(), String> {
+    let output = std::process::Command::new("reg")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn is_installed(_app: &AppHandle) -> bool {
+    reg(&["query", VERB_KEYS[0]]).is_ok()
+}
+
+#[cfg(windows)]
+fn install(_app: &AppHandle, exe: &str) -> Result<(), String> {
+    // Explorer substitutes %V with the folder path, which the app turns into
+    // an OpenProject action on launch.
+    let command = format!("\"{exe}\" \"%V\"");
+    let icon = format!("\"{exe}\",0");
+
+    for key in VERB_KEYS {
+        reg(&["add", key, "/ve", "/d", MENU_LABEL, "/f"])?;
+        reg(&["add", key, "/v", "Icon", "/d", &icon, "/f"])?;
+        reg(&[
+            "add",
+            &format!(r"{key}\command"),
+            "/ve",
+            "/d",
+            &command,
+            "/f",
+        ])?;
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn uninstall(app: &AppHandle) -> Result<(), String> {
+    if !is_installed(app) {
+        return Ok(());
+    }
+
+    for key in VERB_KEYS {
+        let _ = reg(&["delete", key, "/f"]);
+    }
+
+    Ok(())
+}
+
+// Finder integration needs a Services entry in the bundle's Info.plist or a
+// signed Finder Sync extension, neither of which can be installed at runtime.
+#[cfg(not(any(target_os = "linux", windows)))]
+fn is_installed(_app: &AppHandle) -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn install(_app: &AppHandle, _exe: &str) -> Result<(), String> {
+    Err("Shell integration is not supported on this platform".to_string())
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn uninstall(_app: &AppHandle) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn service_menu_opens_directories() {
+        let entry = service_menu("/opt/OpenCode/opencode");
+        assert!(entry.contains("Type=Service\n"));
+        assert!(entry.contains("Exec=\"/opt/OpenCode/opencode\" %F\n"));
+    }
+
+    #[test]
+    fn nautilus_script_quotes_exe() {
+        assert_eq!(
+            nautilus_script("/opt/it's/opencode"),
+            "#!/bin/sh\nexec '/opt/it'\"'\"'s/opencode' \"$@\"\n"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::{path::PathBuf, sync::Mutex};
+use tauri::{AppHandle, Manager};
+
+const FILE_NAME: &str = "connections.json";
+
+/// A server connection the desktop app is currently using, as exposed to
+/// external tools (shell prompts, status bars, scripts).
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct Connection {
+    pub url: String,
+    pub sidecar: bool,
+    pub destination: Option<String>,
+    pub directory: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct Contents<'a> {
+    pid: u32,
+    updated: String,
+    connections: &'a [Connection],
+}
+
+/// Mirrors the active connections into `<app local data>/connections.json`.
+/// The file is replaced atomically on every change and removed on exit, so
+/// readers never observe a partially written file.
+pub struct StateFile {
+    path: PathBuf,
+    connections: Mutex<Vec<Connection>>,
+}
+
+impl StateFile {
+    pub fn init(app: &AppHandle) {
+        let Ok(dir) = app.path().app_local_data_dir() else {
+            tracing::warn!("Could not resolve app local data dir, state file disabled");
+            return;
+        };
+
+        app.manage(Self {
+            path: dir.join(FILE_NAME),
+            connections: Mutex::new(Vec::new()),
+        });
+    }
+
+    pub fn add(&self, connection: Connection) {
+        let mut connections = self.connections.lock().unwrap();
+        connections.retain(|v| v.url != connection.url);
+        connections.push(connection);
+        self.write(&connections);
+    }
+
+    pub fn remove(&self, url: &str) {
+        let mut connections = self.connections.lock().unwrap();
+        connections.retain(|v| v.url != url);
+        self.write(&connections);
+    }
+
+    pub fn clear(&self) {
+        self.connections.lock().unwrap().clear();
+        let _ = std::fs::remove_file(&self.path);
+    }
+
+    fn write(&self, connections: &[Connection]) {
+        if let Err(e) = self.try_write(connections) {
+            tracing::warn!("Failed to write state file: {e}");
+        }
+    }
+
+    fn try_write(&self, connections: &[Connection]) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create state dir: {}", e))?;
+        }
+
+        let json = serde_json::to_string_pretty(&Contents {
+            pid: std::process::id(),
+            updated: chrono::Utc::now().to_rfc3339(),
+            connections,
+        })
+        .map_err(|e| format!("Failed to serialize state: {}", e))?;
+
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("Failed to write state: {}", e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| format!("Failed to replace state: {}", e))
+    }
+}
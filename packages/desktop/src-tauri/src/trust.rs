@@ -0,0 +1,155 @@
+use std::path::Path;
+use tauri::AppHandle;
+use tauri_plugin_store::StoreExt;
+
+use crate::{
+    constants::{SETTINGS_STORE, TRUSTED_WORKSPACES_KEY},
+    projects::ProjectLocation,
+    server,
+};
+
+/// A trust decision for a workspace root. It covers every directory below
+/// `path` on the same host unless a deeper entry says otherwise.
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct TrustEntry {
+    pub path: String,
+    pub host: Option<String>,
+    pub trusted: bool,
+}
+
+fn load(app: &AppHandle) -> Result<Vec<TrustEntry>, String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    Ok(store
+        .get(TRUSTED_WORKSPACES_KEY)
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default())
+}
+
+fn save(app: &AppHandle, entries: &[TrustEntry]) -> Result<(), String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    let value = serde_json::to_value(entries)
+        .map_err(|e| format!("Failed to serialize trust store: {}", e))?;
+    store.set(TRUSTED_WORKSPACES_KEY, value);
+
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+// The deepest entry containing the location wins. Paths are compared by
+// component, so trusting /src/app doesn't cover /src/app-old.
+fn decision(entries: &[TrustEntry], location: &ProjectLocation) -> Option<bool> {
+    entries
+        .iter()
+        .filter(|v| v.host == location.host && Path::new(&location.path).starts_with(&v.path))
+        .max_by_key(|v| Path::new(&v.path).components().count())
+        .map(|v| v.trusted)
+}
+
+// Local paths are matched in the canonical form servers are started with.
+fn canonical(mut location: ProjectLocation) -> Result<ProjectLocation, String> {
+    if location.host.is_none() {
+        location.path = server::canonical_directory(&location.path)?;
+    }
+    Ok(location)
+}
+
+/// Fails unless the user has explicitly trusted the workspace. Checked before
+/// starting a project server, opening a project window (which is also how
+/// deep-link and command-line OpenProject actions open one) and running git.
+///
+/// The shared sidecar is not gated: it is started once for every project and
+/// takes the directory per request over its API, so the frontend must check
+/// `trust_get` before pointing it at a workspace.
+pub fn require(app: &AppHandle, location: &ProjectLocation) -> Result<(), String> {
+    match decision(&load(app)?, location) {
+        Some(true) => Ok(()),
+        Some(false) => Err(format!("Workspace is not trusted: {}", location.path)),
+        None => Err(format!("Workspace trust not decided: {}", location.path)),
+    }
+}
+
+/// Returns whether `location` is trusted, or `None` if the user hasn't been
+/// asked yet.
+#[tauri::command]
+#[specta::specta]
+pub fn trust_get(app: AppHandle, location: ProjectLocation) -> Result<Option<bool>, String> {
+    Ok(decision(&load(&app)?, &canonical(location)?))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn trust_set(app: AppHandle, location: ProjectLocation, trusted: bool) -> Result<(), String> {
+    let location = canonical(location)?;
+    let mut entries = load(&app)?;
+    entries.retain(|v| !(v.path == location.path && v.host == location.host));
+    entries.push(TrustEntry {
+        path: location.path,
+        host: location.host,
+        trusted,
+    });
+
+    tracing::info!(trusted, "Workspace trust changed");
+    save(&app, &entries)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn trust_list(app: AppHandle) -> Result<Vec<TrustEntry>, String> {
+    load(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, trusted: bool) -> TrustEntry {
+        TrustEntry {
+            path: path.to_string(),
+            host: None,
+            trusted,
+        }
+    }
+
+    fn location(path: &str) -> ProjectLocation {
+        ProjectLocation {
+            path: path.to_string(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn deepest_decision_wins() {
+        let entries = [entry("/src", true), entry("/src/vendor", false)];
+
+        assert_eq!(decision(&entries, &location("/src/app")), Some(true));
+        assert_eq!(
+            decision(&entries, &location("/src/vendor/lib")),
+            Some(false)
+        );
+        assert_eq!(decision(&entries, &location("/tmp")), None);
+    }
+
+    #[test]
+    fn matches_whole_components_on_the_same_host() {
+        let entries = [entry("/src/app", true)];
+
+        assert_eq!(decision(&entries, &location("/src/app-old")), None);
+        assert_eq!(
+            decision(
+                &entries,
+                &ProjectLocation {
+                    path: "/src/app".into(),
+                    host: Some("devbox".into()),
+                }
+            ),
+            None
+        );
+    }
+}
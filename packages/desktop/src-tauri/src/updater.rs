@@ -0,0 +1,235 @@
+use std::{sync::Mutex, time::Duration};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+use tauri_plugin_updater::{Update, UpdaterExt};
+use tauri_specta::Event;
+
+use crate::constants::{SETTINGS_STORE, UPDATE_CHANNEL_KEY, UPDATE_DEFERRAL_KEY, UPDATER_ENABLED};
+
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+    Nightly,
+}
+
+impl UpdateChannel {
+    /// The feed overriding the bundle config's endpoints, which stable uses.
+    fn endpoint(&self) -> Option<&'static str> {
+        match self {
+            Self::Stable => None,
+            Self::Beta => option_env!("OPENCODE_UPDATER_BETA_ENDPOINT"),
+            Self::Nightly => option_env!("OPENCODE_UPDATER_NIGHTLY_ENDPOINT"),
+        }
+    }
+
+    /// Beta and nightly only exist in builds that were given a feed for them.
+    fn available(&self) -> bool {
+        *self == Self::Stable || self.endpoint().is_some()
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub notes: Option<String>,
+}
+
+/// "Remind me later" for one version, until an RFC 3339 timestamp.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+struct Deferral {
+    version: String,
+    until: String,
+}
+
+/// Download progress in bytes. `total` is `None` if the server didn't say.
+#[derive(tauri_specta::Event, serde::Serialize, serde::Deserialize, specta::Type, Clone, Debug)]
+pub struct UpdateProgress {
+    pub downloaded: u32,
+    pub total: Option<u32>,
+}
+
+/// The update found by the last `update_check`, waiting for `update_install`.
+#[derive(Default)]
+pub struct PendingUpdate(Mutex<Option<Update>>);
+
+fn deferred(
+    deferral: Option<&Deferral>,
+    version: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> bool {
+    deferral.is_some_and(|v| {
+        v.version == version
+            && chrono::DateTime::parse_from_rfc3339(&v.until).is_ok_and(|until| now < until)
+    })
+}
+
+fn store_get<T: serde::de::DeserializeOwned>(app: &AppHandle, key: &str) -> Option<T> {
+    app.store(SETTINGS_STORE)
+        .ok()
+        .and_then(|store| store.get(key))
+        .and_then(|v| serde_json::from_value(v).ok())
+}
+
+fn store_set(app: &AppHandle, key: &str, value: impl serde::Serialize) -> Result<(), String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("Failed to open settings store: {}", e))?;
+
+    let value =
+        serde_json::to_value(value).map_err(|e| format!("Failed to serialize {}: {}", key, e))?;
+    store.set(key, value);
+
+    store
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+fn channel(app: &AppHandle) -> UpdateChannel {
+    store_get(app, UPDATE_CHANNEL_KEY).unwrap_or(UpdateChannel::Stable)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_update_channel(app: AppHandle) -> UpdateChannel {
+    channel(&app)
+}
+
+/// Channels this build has a feed for.
+#[tauri::command]
+#[specta::specta]
+pub fn list_update_channels() -> Vec<UpdateChannel> {
+    [
+        UpdateChannel::Stable,
+        UpdateChannel::Beta,
+        UpdateChannel::Nightly,
+    ]
+    .into_iter()
+    .filter(UpdateChannel::available)
+    .collect()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_update_channel(app: AppHandle, channel: UpdateChannel) -> Result<(), String> {
+    if !channel.available() {
+        return Err(format!("No {:?} update feed in this build", channel));
+    }
+    store_set(&app, UPDATE_CHANNEL_KEY, channel)
+}
+
+/// Skips `version` in non-forced checks for the given number of hours.
+#[tauri::command]
+#[specta::specta]
+pub fn update_defer(app: AppHandle, version: String, hours: u32) -> Result<(), String> {
+    let until = chrono::Utc::now() + chrono::Duration::hours(hours.into());
+    store_set(
+        &app,
+        UPDATE_DEFERRAL_KEY,
+        Deferral {
+            version,
+            until: until.to_rfc3339(),
+        },
+    )
+}
+
+/// Checks the selected channel's feed. A deferred version is reported as no
+/// update unless `force` is set (e.g. a manual "Check for Updates").
+#[tauri::command]
+#[specta::specta]
+pub async fn update_check(app: AppHandle, force: bool) -> Result<Option<UpdateInfo>, String> {
+    if !UPDATER_ENABLED {
+        return Err("Updates are disabled in this build".to_string());
+    }
+
+    let channel = channel(&app);
+    let mut builder = app.updater_builder().timeout(Duration::from_secs(30));
+    // Channels can be dropped from a later build; fall back to stable then.
+    if let Some(endpoint) = channel.endpoint() {
+        let url: tauri::Url = endpoint
+            .parse()
+            .map_err(|e| format!("Invalid update feed {}: {}", endpoint, e))?;
+        builder = builder
+            .endpoints(vec![url])
+            .map_err(|e| format!("Invalid update feed {}: {}", endpoint, e))?;
+    }
+
+    let update = builder
+        .build()
+        .map_err(|e| format!("Failed to create updater: {}", e))?
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+
+    let update = update.filter(|update| {
+        force
+            || !deferred(
+                store_get::<Deferral>(&app, UPDATE_DEFERRAL_KEY).as_ref(),
+                &update.version,
+                chrono::Utc::now(),
+            )
+    });
+
+    let info = update.as_ref().map(|update| UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        notes: update.body.clone(),
+    });
+    *app.state::<PendingUpdate>().0.lock().unwrap() = update;
+
+    Ok(info)
+}
+
+/// Downloads and installs the update from the last check, emitting
+/// `UpdateProgress`. The caller stops the sidecar and relaunches, as on
+/// Windows the installer replaces the running app.
+#[tauri::command]
+#[specta::specta]
+pub async fn update_install(app: AppHandle) -> Result<(), String> {
+    let update = app
+        .state::<PendingUpdate>()
+        .0
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "No update to install".to_string())?;
+
+    let mut downloaded: usize = 0;
+    update
+        .download_and_install(
+            |chunk, total| {
+                downloaded += chunk;
+                let _ = UpdateProgress {
+                    downloaded: u32::try_from(downloaded).unwrap_or(u32::MAX),
+                    total: total.map(|v| u32::try_from(v).unwrap_or(u32::MAX)),
+                }
+                .emit(&app);
+            },
+            || tracing::info!("Update downloaded"),
+        )
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deferral_applies_to_its_version_until_expiry() {
+        let at = |v: &str| v.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let deferral = Deferral {
+            version: "1.2.0".to_string(),
+            until: "2026-01-02T00:00:00Z".to_string(),
+        };
+        let before = at("2026-01-01T00:00:00Z");
+        let after = at("2026-01-03T00:00:00Z");
+
+        assert!(deferred(Some(&deferral), "1.2.0", before));
+        assert!(!deferred(Some(&deferral), "1.2.0", after));
+        assert!(!deferred(Some(&deferral), "1.3.0", before));
+        assert!(!deferred(None, "1.2.0", before));
+    }
+}
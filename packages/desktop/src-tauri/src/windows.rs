@@ -1,8 +1,10 @@
 use crate::{
     constants::{UPDATER_ENABLED, window_state_flags},
-    server::get_wsl_config,
+    projects,
+    server::{self, ProjectServer, get_wsl_config},
+    trust,
 };
-use std::{ops::Deref, time::Duration};
+use std::{collections::HashMap, ops::Deref, sync::Mutex, time::Duration};
 use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
 use tauri_plugin_window_state::AppHandleExt;
 use tokio::sync::mpsc;
@@ -14,7 +16,11 @@ use std::sync::OnceLock;
 fn use_decorations() -> bool {
     static DECORATIONS: OnceLock<bool> = OnceLock::new();
     *DECORATIONS.get_or_init(|| {
-        crate::linux_windowing::use_decorations(&crate::linux_windowing::SessionEnv::capture())
+        let mut env = crate::linux_windowing::SessionEnv::capture();
+        if env.oc_linux_decorations.is_none() {
+            env.oc_linux_decorations = crate::linux_display::read_decorations();
+        }
+        crate::linux_windowing::use_decorations(&env)
     })
 }
 
@@ -115,6 +121,247 @@ fn setup_window_state_listener(app: &AppHandle, window: &WebviewWindow) {
     });
 }
 
+/// A window bound to one project directory and its dedicated local server.
+pub struct ProjectWindow(WebviewWindow);
+
+impl Deref for ProjectWindow {
+    type Target = WebviewWindow;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Project directory of every open project window, keyed by window label.
+#[derive(Default)]
+pub struct ProjectWindows(Mutex<HashMap<String, String>>);
+
+impl ProjectWindow {
+    pub const LABEL_PREFIX: &str = "project-";
+
+    pub fn create(app: &AppHandle, server: &ProjectServer) -> Result<Self, String> {
+        let windows = app.state::<ProjectWindows>();
+
+        let existing = windows
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, directory)| **directory == server.directory)
+            .map(|(label, _)| label.clone());
+        if let Some(window) = existing.and_then(|label| app.get_webview_window(&label)) {
+            let _ = window.unminimize();
+            let _ = window.set_focus();
+            return Ok(Self(window));
+        }
+
+        let label = format!(
+            "{}{}",
+            Self::LABEL_PREFIX,
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        let title = std::path::Path::new(&server.directory)
+            .file_name()
+            .map(|v| format!("OpenCode - {}", v.to_string_lossy()))
+            .unwrap_or_else(|| "OpenCode".to_string());
+        let server_json = serde_json::to_string(server)
+            .map_err(|e| format!("Failed to serialize server: {}", e))?;
+
+        let window = base_window_config(
+            WebviewWindowBuilder::new(app, &label, WebviewUrl::App("/".into())),
+            app,
+            use_decorations(),
+        )
+        .title(title)
+        .disable_drag_drop_handler()
+        .zoom_hotkeys_enabled(false)
+        .visible(true)
+        .inner_size(1280.0, 800.0)
+        .initialization_script(format!(
+            r#"
+            window.__OPENCODE__ ??= {{}};
+            window.__OPENCODE__.updaterEnabled = {UPDATER_ENABLED};
+            window.__OPENCODE__.projectServer = {server_json};
+          "#
+        ))
+        .build()
+        .map_err(|e| format!("Failed to create window: {}", e))?;
+
+        let _ = window.set_focus();
+
+        #[cfg(windows)]
+        {
+            use tauri_plugin_decorum::WebviewWindowExt;
+            let _ = window.create_overlay_titlebar();
+        }
+
+        windows
+            .0
+            .lock()
+            .unwrap()
+            .insert(label.clone(), server.directory.clone());
+
+        // Stop the project's server once its last window is gone.
+        let handle = app.clone();
+        window.on_window_event(move |event| {
+            if !matches!(event, tauri::WindowEvent::Destroyed) {
+                return;
+            }
+
+            let windows = handle.state::<ProjectWindows>();
+            let mut windows = windows.0.lock().unwrap();
+            let Some(directory) = windows.remove(&label) else {
+                return;
+            };
+            if !windows.values().any(|v| *v == directory) {
+                let _ = server::stop_project(&handle, &directory);
+            }
+        });
+
+        Ok(Self(window))
+    }
+}
+
+#[derive(serde::Serialize, specta::Type, Clone, Debug)]
+pub struct WindowInfo {
+    pub label: String,
+    pub title: String,
+    pub directory: Option<String>,
+    pub focused: bool,
+}
+
+/// Opens (or focuses) a window for `directory`, starting its local server first
+/// so the window is created with working connection details. Refused for
+/// workspaces the user hasn't trusted.
+#[tauri::command]
+#[specta::specta]
+pub async fn window_open_project(app: AppHandle, directory: String) -> Result<String, String> {
+    let directory = server::canonical_directory(&directory)?;
+    trust::require(
+        &app,
+        &projects::ProjectLocation {
+            path: directory.clone(),
+            host: None,
+        },
+    )?;
+
+    let server = server::start_project(&app, &directory).await?;
+    let window = ProjectWindow::create(&app, &server)?;
+
+    let location = projects::ProjectLocation {
+        path: server.directory.clone(),
+        host: None,
+    };
+    if let Err(e) = projects::record(&app, location) {
+        tracing::warn!("Failed to record recent project: {e}");
+    }
+
+    Ok(window.label().to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn window_list(app: AppHandle) -> Vec<WindowInfo> {
+    let directories = app.state::<ProjectWindows>().0.lock().unwrap().clone();
+
+    let mut windows = app
+        .webview_windows()
+        .into_iter()
+        .filter(|(label, _)| label != LoadingWindow::LABEL)
+        .map(|(label, window)| WindowInfo {
+            title: window.title().unwrap_or_default(),
+            directory: directories.get(&label).cloned(),
+            focused: window.is_focused().unwrap_or(false),
+            label,
+        })
+        .collect::<Vec<_>>();
+    windows.sort_by(|a, b| a.label.cmp(&b.label));
+    windows
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn window_focus(app: AppHandle, label: String) -> Result<(), String> {
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Unknown window: {label}"))?;
+
+    let _ = window.unminimize();
+    window
+        .set_focus()
+        .map_err(|e| format!("Failed to focus window: {}", e))
+}
+
+// The badge and progress bar show on the app's dock, launcher or taskbar
+// icon, so they can go through any open window. Prefer the focused one.
+fn icon_window(app: &AppHandle) -> Option<WebviewWindow> {
+    let windows = app.webview_windows();
+    windows
+        .values()
+        .find(|v| v.is_focused().unwrap_or(false))
+        .or_else(|| windows.values().next())
+        .cloned()
+}
+
+/// Shows `count` sessions awaiting approval on the dock (macOS) or launcher
+/// (Linux, Unity launcher API) icon. Zero clears the badge. Does nothing while
+/// no window is open.
+pub fn set_badge(app: &AppHandle, count: u32) -> Result<(), String> {
+    let Some(window) = icon_window(app) else {
+        return Ok(());
+    };
+
+    window
+        .set_badge_count((count > 0).then_some(count as i64))
+        .map_err(|e| format!("Failed to set badge count: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_badge_count(app: AppHandle, count: u32) -> Result<(), String> {
+    set_badge(&app, count)
+}
+
+#[derive(serde::Deserialize, specta::Type, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskbarProgress {
+    None,
+    Normal,
+    Indeterminate,
+    Paused,
+    Error,
+}
+
+/// Reports a long-running operation (e.g. an update download) on the taskbar
+/// button (Windows), dock icon (macOS) or launcher entry (Linux).
+/// `progress` is a percentage and ignored for `none` and `indeterminate`.
+#[tauri::command]
+#[specta::specta]
+pub fn set_taskbar_progress(
+    app: AppHandle,
+    state: TaskbarProgress,
+    progress: Option<u32>,
+) -> Result<(), String> {
+    use tauri::window::{ProgressBarState, ProgressBarStatus};
+
+    let window = icon_window(&app).ok_or_else(|| "No window is open".to_string())?;
+
+    let status = match state {
+        TaskbarProgress::None => ProgressBarStatus::None,
+        TaskbarProgress::Normal => ProgressBarStatus::Normal,
+        TaskbarProgress::Indeterminate => ProgressBarStatus::Indeterminate,
+        TaskbarProgress::Paused => ProgressBarStatus::Paused,
+        TaskbarProgress::Error => ProgressBarStatus::Error,
+    };
+
+    window
+        .set_progress_bar(ProgressBarState {
+            status: Some(status),
+            progress: progress.map(|v| v.min(100) as u64),
+        })
+        .map_err(|e| format!("Failed to set taskbar progress: {}", e))
+}
+
 pub struct LoadingWindow(WebviewWindow);
 
 impl Deref for LoadingWindow {
@@ -164,6 +411,12 @@ fn base_window_config<'a, R: Runtime, M: Manager<R>>(
         .data_directory(_app.path().config_dir().expect("Failed to get config dir").join(_app.config().product_name.clone().unwrap()))
         .decorations(false);
 
+    #[cfg(windows)]
+    let window_builder = match crate::proxy::webview_url() {
+        Some(url) => window_builder.proxy_url(url),
+        None => window_builder,
+    };
+
     #[cfg(target_os = "macos")]
     let window_builder = window_builder
         .title_bar_style(tauri::TitleBarStyle::Overlay)
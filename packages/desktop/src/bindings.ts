@@ -12,27 +12,236 @@ export const commands = {
 	setDefaultServerUrl: (url: string | null) => __TAURI_INVOKE<null>("set_default_server_url", { url }),
 	getWslConfig: () => __TAURI_INVOKE<WslConfig>("get_wsl_config"),
 	setWslConfig: (config: WslConfig) => __TAURI_INVOKE<null>("set_wsl_config", { config }),
+	getLocalServerConfig: () => __TAURI_INVOKE<LocalServerConfig>("get_local_server_config"),
+	setLocalServerConfig: (config: LocalServerConfig) => __TAURI_INVOKE<null>("set_local_server_config", { config }),
+	startProjectServer: (directory: string) => __TAURI_INVOKE<ProjectServer>("start_project_server", { directory }),
+	stopProjectServer: (directory: string) => __TAURI_INVOKE<null>("stop_project_server", { directory }),
+	listProjectServers: () => __TAURI_INVOKE<ProjectServer[]>("list_project_servers"),
+	getServerHealth: () => __TAURI_INVOKE<ServerHealth[]>("get_server_health"),
+	serverLogs: (lines: number, filter: string | null) => __TAURI_INVOKE<ServerLogLine[]>("server_logs", { lines, filter }),
+	serverLogsFollow: (filter: string | null, events: Channel) => __TAURI_INVOKE<number>("server_logs_follow", { filter, events }),
+	serverLogsUnfollow: (id: number) => __TAURI_INVOKE<null>("server_logs_unfollow", { id }),
+	runDiagnostics: () => __TAURI_INVOKE<DiagnosticsReport>("run_diagnostics"),
+	windowOpenProject: (directory: string) => __TAURI_INVOKE<string>("window_open_project", { directory }),
+	windowList: () => __TAURI_INVOKE<WindowInfo[]>("window_list"),
+	windowFocus: (label: string) => __TAURI_INVOKE<null>("window_focus", { label }),
+	setBadgeCount: (count: number) => __TAURI_INVOKE<null>("set_badge_count", { count }),
+	setTaskbarProgress: (state: TaskbarProgress, progress: number | null) => __TAURI_INVOKE<null>("set_taskbar_progress", { state, progress }),
+	getAutostart: () => __TAURI_INVOKE<boolean>("get_autostart"),
+	setAutostart: (enabled: boolean) => __TAURI_INVOKE<null>("set_autostart", { enabled }),
+	getBackgroundMode: () => __TAURI_INVOKE<boolean>("get_background_mode"),
+	setBackgroundMode: (enabled: boolean) => __TAURI_INVOKE<null>("set_background_mode", { enabled }),
+	powerInhibit: (reason: string) => __TAURI_INVOKE<null>("power_inhibit", { reason }),
+	powerRelease: (reason: string) => __TAURI_INVOKE<null>("power_release", { reason }),
+	getPreventSleep: () => __TAURI_INVOKE<boolean>("get_prevent_sleep"),
+	setPreventSleep: (enabled: boolean) => __TAURI_INVOKE<null>("set_prevent_sleep", { enabled }),
+	pickProject: () => __TAURI_INVOKE<ProjectLocation | null>("pick_project"),
+	listRecentProjects: () => __TAURI_INVOKE<RecentProject[]>("list_recent_projects"),
+	addRecentProject: (location: ProjectLocation) => __TAURI_INVOKE<null>("add_recent_project", { location }),
+	pinRecentProject: (location: ProjectLocation, pinned: boolean) => __TAURI_INVOKE<null>("pin_recent_project", { location, pinned }),
+	removeRecentProject: (location: ProjectLocation) => __TAURI_INVOKE<null>("remove_recent_project", { location }),
+	getShellIntegration: () => __TAURI_INVOKE<boolean>("get_shell_integration"),
+	installShellIntegration: () => __TAURI_INVOKE<null>("install_shell_integration"),
+	uninstallShellIntegration: () => __TAURI_INVOKE<null>("uninstall_shell_integration"),
+	gitStatus: (directory: string) => __TAURI_INVOKE<GitFileStatus[]>("git_status", { directory }),
+	gitDiff: (directory: string, path: string | null, staged: boolean) => __TAURI_INVOKE<string>("git_diff", { directory, path, staged }),
+	gitLog: (directory: string, n: number) => __TAURI_INVOKE<GitCommit[]>("git_log", { directory, n }),
+	gitBranchList: (directory: string) => __TAURI_INVOKE<GitBranch[]>("git_branch_list", { directory }),
+	trustGet: (location: ProjectLocation) => __TAURI_INVOKE<boolean | null>("trust_get", { location }),
+	trustSet: (location: ProjectLocation, trusted: boolean) => __TAURI_INVOKE<null>("trust_set", { location, trusted }),
+	trustList: () => __TAURI_INVOKE<TrustEntry[]>("trust_list"),
+	getEnvProfile: (directory: string) => __TAURI_INVOKE<EnvVar[]>("get_env_profile", { directory }),
+	setEnvProfile: (directory: string, vars: EnvVar[]) => __TAURI_INVOKE<null>("set_env_profile", { directory, vars }),
+	settingsGet: () => __TAURI_INVOKE<Settings>("settings_get"),
+	settingsSet: (settings: Settings) => __TAURI_INVOKE<null>("settings_set", { settings }),
 	getDisplayBackend: () => __TAURI_INVOKE<"wayland" | "auto" | null>("get_display_backend"),
 	setDisplayBackend: (backend: LinuxDisplayBackend) => __TAURI_INVOKE<null>("set_display_backend", { backend }),
 	parseMarkdownCommand: (markdown: string) => __TAURI_INVOKE<string>("parse_markdown_command", { markdown }),
 	checkAppExists: (appName: string) => __TAURI_INVOKE<boolean>("check_app_exists", { appName }),
 	wslPath: (path: string, mode: "windows" | "linux" | null) => __TAURI_INVOKE<string>("wsl_path", { path, mode }),
 	resolveAppPath: (appName: string) => __TAURI_INVOKE<string | null>("resolve_app_path", { appName }),
+	getPrivacyMode: () => __TAURI_INVOKE<boolean>("get_privacy_mode"),
+	setPrivacyMode: (enabled: boolean) => __TAURI_INVOKE<null>("set_privacy_mode", { enabled }),
+	proxyReload: () => __TAURI_INVOKE<string | null>("proxy_reload"),
+	getProxyConfig: () => __TAURI_INVOKE<ProxyConfig>("get_proxy_config"),
+	setProxyConfig: (config: ProxyConfig, password: string | null) => __TAURI_INVOKE<null>("set_proxy_config", { config, password }),
+	getExtraCaCerts: () => __TAURI_INVOKE<string[]>("get_extra_ca_certs"),
+	setExtraCaCerts: (paths: string[]) => __TAURI_INVOKE<null>("set_extra_ca_certs", { paths }),
+	logSetLevel: (filter: string) => __TAURI_INVOKE<null>("log_set_level", { filter }),
+	logOpenFolder: () => __TAURI_INVOKE<null>("log_open_folder"),
+	getUpdateChannel: () => __TAURI_INVOKE<UpdateChannel>("get_update_channel"),
+	listUpdateChannels: () => __TAURI_INVOKE<UpdateChannel[]>("list_update_channels"),
+	setUpdateChannel: (channel: UpdateChannel) => __TAURI_INVOKE<null>("set_update_channel", { channel }),
+	updateDefer: (version: string, hours: number) => __TAURI_INVOKE<null>("update_defer", { version, hours }),
+	updateCheck: (force: boolean) => __TAURI_INVOKE<UpdateInfo | null>("update_check", { force }),
+	updateInstall: () => __TAURI_INVOKE<null>("update_install"),
+	confirmDeepLink: (id: string, accept: boolean, remember: boolean) => __TAURI_INVOKE<null>("confirm_deep_link", { id, accept, remember }),
+	createAuthState: (provider: string) => __TAURI_INVOKE<string>("create_auth_state", { provider }),
+	drainPendingDeepLinks: () => __TAURI_INVOKE<PendingDeepLinks>("drain_pending_deep_links"),
+	createDeepLink: (directory: string, session: string | null, copy: boolean) => __TAURI_INVOKE<string>("create_deep_link", { directory, session, copy }),
+	resolveShareLink: (url: string) => __TAURI_INVOKE<ShareTarget>("resolve_share_link", { url }),
+	getDeepLinkPolicy: () => __TAURI_INVOKE<PolicyEntry[]>("get_deep_link_policy"),
+	setDeepLinkPolicy: (kind: string, decision: Decision | null) => __TAURI_INVOKE<null>("set_deep_link_policy", { kind, decision }),
 };
 
 /** Events */
 export const events = {
+	deepLinkBatch: makeEvent<DeepLinkBatch>("deep-link-batch"),
+	deepLinkConfirmation: makeEvent<DeepLinkConfirmation>("deep-link-confirmation"),
 	loadingWindowComplete: makeEvent<LoadingWindowComplete>("loading-window-complete"),
+	networkChanged: makeEvent<NetworkChanged>("network-changed"),
+	serverCrashed: makeEvent<ServerCrashed>("server-crashed"),
+	serverHealth: makeEvent<ServerHealth>("server-health"),
+	serverRestarted: makeEvent<ServerRestarted>("server-restarted"),
+	settingsChanged: makeEvent<SettingsChanged>("settings-changed"),
 	sqliteMigrationProgress: makeEvent<SqliteMigrationProgress>("sqlite-migration-progress"),
+	updateProgress: makeEvent<UpdateProgress>("update-progress"),
 };
 
 /* Types */
+export type CheckStatus = "ok" | "warn" | "fail" | "skipped";
+
+export type Decision = "allow" | "ask" | "deny";
+
+export type DeepLinkAction = { type: "open_project"; directory: string; session: string | null } | { type: "open_share"; id: string; url: string } | { type: "new_session"; directory: string; prompt: string | null; model: string | null; agent: string | null } | { type: "open_file"; directory: string; path: string; line: number | null; column: number | null; session: string | null } | { type: "auth_callback"; provider: string; code: string; state: string } | { type: "run_command"; command: string; args: string | null; directory: string };
+
+export type DeepLinkBatch = {
+		seq: number,
+		actions: DeepLinkAction[],
+	};
+
+export type DeepLinkConfirmation = {
+		id: string,
+		action: DeepLinkAction,
+	};
+
+export type DiagnosticCheck = {
+		name: string,
+		status: CheckStatus,
+		detail: string,
+	};
+
+export type DiagnosticsReport = {
+		app_version: string,
+		os: string,
+		arch: string,
+		generated_at: string,
+		checks: DiagnosticCheck[],
+	};
+
+export type EnvVar = {
+		key: string,
+		value: string,
+	};
+
+export type GitBranch = {
+		name: string,
+		current: boolean,
+		upstream: string | null,
+	};
+
+export type GitCommit = {
+		hash: string,
+		author: string,
+		date: string,
+		subject: string,
+	};
+
+export type GitFileStatus = {
+		path: string,
+		original_path: string | null,
+		index: string,
+		worktree: string,
+	};
+
+export type HealthStatus = "healthy" | "degraded" | "down";
+
 export type InitStep = { phase: "server_waiting" } | { phase: "sqlite_waiting" } | { phase: "done" };
 
+export type LinuxDecorations = "auto" | "native" | "none";
+
 export type LinuxDisplayBackend = "wayland" | "auto";
 
 export type LoadingWindowComplete = null;
 
+export type LocalServerConfig = {
+		hostname: string | null,
+		port: number | null,
+		lan_access: boolean,
+	};
+
+export type LogStream = "stdout" | "stderr";
+
+export type NetworkChanged = {
+		online: boolean,
+		local_address: string | null,
+	};
+
+export type PendingDeepLinks = {
+		batches: DeepLinkBatch[],
+		confirmations: DeepLinkConfirmation[],
+	};
+
+export type PolicyEntry = {
+		kind: string,
+		decision: Decision,
+	};
+
+export type ProjectLocation = {
+		path: string,
+		host: string | null,
+	};
+
+export type ProjectServer = {
+		directory: string,
+		url: string,
+		username: string,
+		password: string,
+	};
+
+export type ProxyConfig = {
+		mode: ProxyMode,
+		url: string | null,
+		username: string | null,
+		has_password: boolean,
+		bypass: string[],
+	};
+
+export type ProxyMode = "system" | "manual" | "none";
+
+export type RecentProject = {
+		path: string,
+		host: string | null,
+		last_opened: string,
+		pinned: boolean,
+	};
+
+export type ServerCrashed = {
+		directory: string | null,
+		code: number | null,
+		signal: number | null,
+		logs: string[],
+		restarting: boolean,
+	};
+
+export type ServerHealth = {
+		url: string,
+		directory: string | null,
+		status: HealthStatus,
+		latency_ms: number | null,
+		consecutive_failures: number,
+		version: string | null,
+		version_mismatch: boolean,
+	};
+
+export type ServerLogLine = {
+		seq: number,
+		timestamp: string,
+		stream: LogStream,
+		directory: string | null,
+		text: string,
+	};
+
 export type ServerReadyData = {
 		url: string,
 		username: string | null,
@@ -40,8 +249,56 @@ export type ServerReadyData = {
 		is_sidecar: boolean,
 	};
 
+export type ServerRestarted = {
+		directory: string | null,
+		attempt: number,
+	};
+
+export type Settings = {
+		default_server_url: string | null,
+		privacy_mode: boolean,
+		background_mode: boolean,
+		prevent_sleep: boolean,
+		prefer_wayland: boolean,
+		linux_decorations: LinuxDecorations | null,
+	};
+
+export type SettingsChanged = {
+		settings: Settings,
+	};
+
+export type ShareTarget = { type: "session"; server: string; directory: string; session: string } | { type: "web"; url: string };
+
 export type SqliteMigrationProgress = { type: "InProgress"; value: number } | { type: "Done" };
 
+export type TaskbarProgress = "none" | "normal" | "indeterminate" | "paused" | "error";
+
+export type TrustEntry = {
+		path: string,
+		host: string | null,
+		trusted: boolean,
+	};
+
+export type UpdateChannel = "stable" | "beta" | "nightly";
+
+export type UpdateInfo = {
+		version: string,
+		current_version: string,
+		notes: string | null,
+	};
+
+export type UpdateProgress = {
+		downloaded: number,
+		total: number | null,
+	};
+
+export type WindowInfo = {
+		label: string,
+		title: string,
+		directory: string | null,
+		focused: boolean,
+	};
+
 export type WslConfig = {
 		enabled: boolean,
 	};